@@ -77,7 +77,7 @@ pub fn bench_nysiis_strict(c: &mut Criterion) {
 }
 
 pub fn bench_nysiis_not_strict(c: &mut Criterion) {
-    let nysiis = Nysiis::new(false);
+    let nysiis = Nysiis::new(None);
     bench_encoder(c, "Nysiis (not strict)", Box::new(nysiis), "Phillipson");
 }
 
@@ -103,6 +103,16 @@ pub fn bench_beider_morse(c: &mut Criterion) {
     c.bench_function("Beider-Morse", |b| b.iter(|| beider_morse.encode("Angelo")));
 }
 
+pub fn bench_beider_morse_singleton(c: &mut Criterion) {
+    let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/")).unwrap();
+    let builder = BeiderMorseBuilder::new(&config_files);
+    let beider_morse = builder.build();
+    let italian = LanguageSet::from(vec!["italian"]);
+    c.bench_function("Beider-Morse (italian)", |b| {
+        b.iter(|| beider_morse.encode_with_languages("Angelo", &italian))
+    });
+}
+
 criterion_group!(
     name = caverphone;
     config = Criterion::default().sample_size(300);
@@ -151,7 +161,7 @@ criterion_group!(
 criterion_group!(
     name = beider_morse;
     config = Criterion::default().sample_size(300);
-    targets = bench_beider_morse
+    targets = bench_beider_morse, bench_beider_morse_singleton
 );
 
 criterion_main!(
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::beider_morse::LanguageSet;
+
+/// Bounded cache of guessed languages, keyed by the lowercased input. When full,
+/// the least recently used entry is evicted.
+///
+/// It is shared between clones of an encoder and can be used from multiple threads.
+#[derive(Debug, Clone)]
+pub(crate) struct LanguageCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    capacity: usize,
+    /// Incremented at each access, it gives the recency of entries.
+    tick: u64,
+    /// Cached languages and last access of each input.
+    entries: HashMap<String, (LanguageSet, u64)>,
+    /// Inputs by last access, the first one is the least recently used.
+    recency: BTreeMap<u64, String>,
+    hits: u64,
+}
+
+impl LanguageCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                tick: 0,
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                hits: 0,
+            })),
+        }
+    }
+
+    /// Return the cached languages of `input`, or compute them with `guess` and cache them.
+    pub(crate) fn get_or_insert_with(
+        &self,
+        input: &str,
+        guess: impl FnOnce() -> LanguageSet,
+    ) -> LanguageSet {
+        let key = input.to_lowercase();
+        {
+            // A poisoned lock only means that another thread panicked, entries are still consistent
+            let mut inner = self.inner.lock().unwrap_or_else(|error| error.into_inner());
+            inner.tick += 1;
+            let tick = inner.tick;
+            if let Some((languages, last_access)) = inner.entries.get_mut(&key) {
+                let previous = std::mem::replace(last_access, tick);
+                let languages = languages.clone();
+                inner.recency.remove(&previous);
+                inner.recency.insert(tick, key);
+                inner.hits += 1;
+                return languages;
+            }
+        }
+
+        // Don't hold the lock while guessing
+        let languages = guess();
+
+        let mut inner = self.inner.lock().unwrap_or_else(|error| error.into_inner());
+        if inner.capacity == 0 || inner.entries.contains_key(&key) {
+            return languages;
+        }
+        if inner.entries.len() >= inner.capacity {
+            let oldest = inner.recency.keys().next().copied();
+            if let Some(oldest) = oldest.and_then(|tick| inner.recency.remove(&tick)) {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.tick += 1;
+        let tick = inner.tick;
+        inner.recency.insert(tick, key.clone());
+        inner.entries.insert(key, (languages.clone(), tick));
+
+        languages
+    }
+
+    /// Number of times languages were found in the cache.
+    #[cfg(test)]
+    pub(crate) fn hits(&self) -> u64 {
+        self.inner
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .hits
+    }
+
+    /// Number of cached inputs.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .entries
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let cache = LanguageCache::new(2);
+        let italian = LanguageSet::from(vec!["italian"]);
+
+        assert_eq!(
+            cache.get_or_insert_with("Angelo", || italian.clone()),
+            italian
+        );
+        assert_eq!(
+            cache.get_or_insert_with("ANGELO", || LanguageSet::Any),
+            italian
+        );
+        assert_eq!(cache.hits(), 1);
+
+        cache.get_or_insert_with("Peter", || LanguageSet::Any);
+        // "Angelo" is the most recently used, "Peter" is evicted
+        cache.get_or_insert_with("angelo", || LanguageSet::Any);
+        cache.get_or_insert_with("Schwarz", || LanguageSet::Any);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.get_or_insert_with("angelo", || LanguageSet::Any),
+            italian
+        );
+        assert_eq!(cache.hits(), 3);
+        assert_eq!(
+            cache.get_or_insert_with("Peter", || LanguageSet::NoLanguages),
+            LanguageSet::NoLanguages
+        );
+        assert_eq!(cache.hits(), 3);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let cache = LanguageCache::new(0);
+
+        cache.get_or_insert_with("Angelo", || LanguageSet::Any);
+        cache.get_or_insert_with("Angelo", || LanguageSet::Any);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.hits(), 0);
+    }
+}
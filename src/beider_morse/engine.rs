@@ -2,9 +2,12 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "bm_language_cache")]
+use crate::beider_morse::cache::LanguageCache;
 use crate::beider_morse::lang::Lang;
 use crate::beider_morse::languages::LanguageSet;
 use crate::beider_morse::rule::{Phoneme, PhonemeList, PrivateRuleType, Rule, Rules};
+use crate::beider_morse::split_phonemes;
 use crate::helper::CharSequence;
 use crate::NameType;
 
@@ -128,6 +131,10 @@ pub(crate) struct PhoneticEngine<'a> {
     pub(crate) rule_type: PrivateRuleType,
     pub(crate) concat: bool,
     pub(crate) max_phonemes: usize,
+    pub(crate) max_input_length: Option<usize>,
+    pub(crate) max_branches: Option<usize>,
+    #[cfg(feature = "bm_language_cache")]
+    pub(crate) language_cache: Option<LanguageCache>,
 }
 
 impl PhoneticEngine<'_> {
@@ -183,29 +190,64 @@ impl PhoneticEngine<'_> {
         PhonemeBuilder { phonemes }
     }
 
+    /// Check if `input` has more characters than the maximum input length.
+    fn is_too_long(&self, input: &str) -> bool {
+        self.max_input_length
+            .map(|max| input.chars().nth(max).is_some())
+            .unwrap_or(false)
+    }
+
     pub fn encode(&self, input: &str) -> String {
-        let languages = self.lang.guess_languages(input);
+        if self.is_too_long(input) {
+            return String::new();
+        }
+
+        let languages = self.guess_languages(input);
         self.encode_with_language_set(input, &languages)
     }
 
+    #[cfg(not(feature = "bm_language_cache"))]
+    fn guess_languages(&self, input: &str) -> LanguageSet {
+        self.lang.guess_languages(input)
+    }
+
+    #[cfg(feature = "bm_language_cache")]
+    fn guess_languages(&self, input: &str) -> LanguageSet {
+        match &self.language_cache {
+            Some(cache) => cache.get_or_insert_with(input, || self.lang.guess_languages(input)),
+            None => self.lang.guess_languages(input),
+        }
+    }
+
+    /// Keep, at most, the lexicographically smallest `max_branches` alternatives.
+    /// When truncated, the blocks are flattened into a single `|` separated list.
+    fn limit_branches(&self, encoded: String) -> String {
+        match self.max_branches {
+            Some(max) => {
+                let mut phonemes = split_phonemes(&encoded);
+                if phonemes.len() <= max {
+                    return encoded;
+                }
+                phonemes.sort_unstable();
+                phonemes.dedup();
+                phonemes.truncate(max);
+                phonemes.join("|")
+            }
+            None => encoded,
+        }
+    }
+
     pub fn encode_with_language_set(&self, input: &str, languages: &LanguageSet) -> String {
-        let l = if languages.is_singleton() {
-            languages.any().unwrap()
-        } else {
-            "any".to_string()
-        };
-        let rules = self
-            .rules
-            .rules(self.name_type, PrivateRuleType::Rules, l.as_str())
-            .unwrap();
-        let final_rules1 = self
-            .rules
-            .rules(self.name_type, self.rule_type, "common")
-            .unwrap();
-        let final_rules2 = self
-            .rules
-            .rules(self.name_type, self.rule_type, l.as_str())
-            .unwrap();
+        // Recursive calls are bounded too, so intermediate results never
+        // grow much larger than the final one.
+        let encoded = self.encode_unbounded(input, languages);
+        self.limit_branches(encoded)
+    }
+
+    fn encode_unbounded(&self, input: &str, languages: &LanguageSet) -> String {
+        if self.is_too_long(input) {
+            return String::new();
+        }
 
         let input = input.to_lowercase().replace('-', " ");
 
@@ -261,7 +303,7 @@ impl PhoneticEngine<'_> {
             .iter()
             .map(|v| {
                 if self.name_type == NameType::Sephardic {
-                    v.split('\'').last().unwrap()
+                    v.split('\'').next_back().unwrap()
                 } else {
                     v
                 }
@@ -279,6 +321,26 @@ impl PhoneticEngine<'_> {
             words.first().unwrap().to_string()
         };
 
+        // Rules are only looked up once the input is a single block, recursive
+        // calls above select their own. A singleton set uses its language rules
+        // directly, without going through "any".
+        let language = match languages {
+            LanguageSet::SomeLanguages(set) if set.len() == 1 => set.iter().next().unwrap(),
+            _ => "any",
+        };
+        let rules = self
+            .rules
+            .rules(self.name_type, PrivateRuleType::Rules, language)
+            .unwrap();
+        let final_rules1 = self
+            .rules
+            .rules(self.name_type, self.rule_type, "common")
+            .unwrap();
+        let final_rules2 = self
+            .rules
+            .rules(self.name_type, self.rule_type, language)
+            .unwrap();
+
         let mut phoneme_builder = &mut PhonemeBuilder::empty(languages);
         let input = CharSequence::from(input.as_str());
         let mut i = 0;
@@ -397,6 +459,10 @@ mod tests {
                 rule_type: (*rule_type).into(),
                 concat: *concat,
                 max_phonemes: *max_phoneme,
+                max_input_length: None,
+                max_branches: None,
+                #[cfg(feature = "bm_language_cache")]
+                language_cache: None,
             };
 
             let result = engine.encode(value);
@@ -431,6 +497,10 @@ mod tests {
             rule_type,
             concat,
             max_phonemes: DEFAULT_MAX_PHONEMES,
+            max_input_length: None,
+            max_branches: None,
+            #[cfg(feature = "bm_language_cache")]
+            language_cache: None,
         };
 
         let language_set: Option<LanguageSet> = args.get("languageSet").and_then(|v| {
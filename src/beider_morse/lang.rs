@@ -4,15 +4,22 @@ use std::path::Path;
 
 use enum_iterator::all;
 use regex::Regex;
+#[cfg(feature = "binary_bm")]
+use serde::{Deserialize, Serialize};
 
 use crate::beider_morse::{LanguageSet, Languages};
 use crate::{build_error, end_of_line, lang, multiline_comment, BMError, NameType, PhoneticError};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 struct LangRule {
     line_number: usize,
     accept_on_match: bool,
     languages: BTreeSet<String>,
+    #[cfg_attr(
+        feature = "binary_bm",
+        serde(with = "crate::beider_morse::serde_regex")
+    )]
     pattern: Regex,
 }
 
@@ -33,6 +40,7 @@ impl LangRule {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "embedded_bm", derive(Default))]
 pub struct Lang {
     languages: BTreeSet<String>,
@@ -63,6 +71,7 @@ impl Lang {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 pub struct Langs {
     langs: BTreeMap<NameType, Lang>,
 }
@@ -80,8 +89,14 @@ impl Default for Langs {
 }
 
 impl Langs {
-    pub fn new(directory: &Path, languages: &Languages) -> Result<Self, PhoneticError> {
-        build_langs(directory, languages)
+    /// Parse language detection files. If files were read from `directory`, it is
+    /// prepended to file names in errors.
+    pub fn new(
+        files: &BTreeMap<&str, &str>,
+        languages: &Languages,
+        directory: Option<&Path>,
+    ) -> Result<Self, PhoneticError> {
+        build_langs(files, languages, directory)
     }
 
     pub fn get(&self, name_type: &NameType) -> Option<&Lang> {
@@ -118,11 +133,15 @@ fn parse_lang(
         }
 
         if let Ok((rm, (pattern, langs, accept_on_match))) = lang()(remains) {
-            remains = rm;
-
             let pattern: Regex = Regex::new(pattern).map_err(|error| {
-                build_error(line_number, filename.clone(), remains, error.to_string())
+                build_error(
+                    line_number,
+                    filename.clone(),
+                    remains,
+                    format!("Invalid pattern : {error}"),
+                )
             })?;
+            remains = rm;
             let langs: BTreeSet<String> =
                 BTreeSet::from_iter(langs.split('+').map(|v| v.to_string()));
             rules.push(LangRule {
@@ -137,7 +156,7 @@ fn parse_lang(
         // Everything fails, then return an error...
         return Err(build_error(
             line_number,
-            None,
+            filename,
             remains,
             "Can't parse line for language detection".to_string(),
         ));
@@ -149,16 +168,26 @@ fn parse_lang(
     })
 }
 
-fn build_langs(directory: &Path, languages_set: &Languages) -> Result<Langs, PhoneticError> {
+fn build_langs(
+    files: &BTreeMap<&str, &str>,
+    languages_set: &Languages,
+    directory: Option<&Path>,
+) -> Result<Langs, PhoneticError> {
     let mut langs: BTreeMap<NameType, Lang> = BTreeMap::new();
 
     for name_type in all::<NameType>() {
-        let languages = languages_set.get(&name_type).unwrap();
-        let filename = directory.join(format!("{name_type}_lang.txt"));
-        let content = std::fs::read_to_string(filename.clone())
-            .map_err(|error| PhoneticError::BMError(BMError::from(error)))?;
-        let filename = filename.to_str().map(|v| v.to_string());
-        let lang = parse_lang(filename, content, languages)?;
+        let languages = languages_set
+            .get(&name_type)
+            .ok_or_else(|| BMError::UnknownNameType(name_type.language_filename()))?;
+        let filename = format!("{name_type}_lang.txt");
+        let content = files.get(filename.as_str()).ok_or_else(|| {
+            BMError::WrongFilename(format!("Can't find file {filename} for language detection"))
+        })?;
+        let filename = match directory {
+            Some(directory) => directory.join(&filename).to_str().map(|v| v.to_string()),
+            None => Some(filename),
+        };
+        let lang = parse_lang(filename, content.to_string(), languages)?;
         langs.insert(name_type, lang);
     }
 
@@ -167,23 +196,51 @@ fn build_langs(directory: &Path, languages_set: &Languages) -> Result<Langs, Pho
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
-
     use super::*;
+    use crate::beider_morse::{files_view, read_directory};
 
     #[test]
     fn test_langs() -> Result<(), PhoneticError> {
-        let path = &PathBuf::from("./test_assets/cc-rules/");
-        let langs = Langs::new(path, &Languages::try_from(path)?)?;
+        let files = read_directory(Path::new("./test_assets/cc-rules/"))?;
+        let files = files_view(&files);
+        let langs = Langs::new(&files, &Languages::try_from(&files)?, None)?;
 
         assert!(!langs.langs.is_empty());
         Ok(())
     }
 
+    #[test]
+    fn test_langs_error_filename() -> Result<(), PhoneticError> {
+        let directory = Path::new("./test_assets/cc-rules/");
+        let files = read_directory(directory)?;
+        let mut files = files_view(&files);
+        let languages = Languages::try_from(&files)?;
+        files.insert("gen_lang.txt", "This is wrong.");
+
+        let filename = |result: Result<Langs, PhoneticError>| match result {
+            Err(PhoneticError::ParseRuleError(error)) => error.filename,
+            result => panic!("Expected a parse error, got {result:?}"),
+        };
+        assert_eq!(
+            filename(Langs::new(&files, &languages, Some(directory))),
+            directory
+                .join("gen_lang.txt")
+                .to_str()
+                .map(|v| v.to_string())
+        );
+        assert_eq!(
+            filename(Langs::new(&files, &languages, None)),
+            Some("gen_lang.txt".to_string())
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_language_guessing() -> Result<(), PhoneticError> {
-        let path = &PathBuf::from("./test_assets/cc-rules/");
-        let langs = Langs::new(path, &Languages::try_from(path)?)?;
+        let files = read_directory(Path::new("./test_assets/cc-rules/"))?;
+        let files = files_view(&files);
+        let langs = Langs::new(&files, &Languages::try_from(&files)?, None)?;
         let langs = langs.get(&NameType::Generic).unwrap();
 
         let data = vec![
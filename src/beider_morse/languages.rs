@@ -2,9 +2,10 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 
+use enum_iterator::all;
 use serde::{Deserialize, Serialize};
 
-use crate::beider_morse::NameType;
+use crate::beider_morse::{files_view, read_directory, NameType};
 use crate::{build_error, end_of_line, language, multiline_comment, PhoneticError};
 
 /// This represents a set of languages.
@@ -140,18 +141,15 @@ impl Default for Languages {
     }
 }
 
-impl TryFrom<&PathBuf> for Languages {
+impl TryFrom<&BTreeMap<&str, &str>> for Languages {
     type Error = PhoneticError;
 
-    fn try_from(directory: &PathBuf) -> Result<Self, Self::Error> {
+    fn try_from(files: &BTreeMap<&str, &str>) -> Result<Self, Self::Error> {
         let mut map: BTreeMap<NameType, BTreeSet<String>> = BTreeMap::new();
-        let paths = std::fs::read_dir(directory)?;
 
-        for path in paths {
-            let path = path?;
-            if let Ok(name_type) = NameType::try_from(path.file_name()) {
-                let content = std::fs::read_to_string(path.path())?;
-                let languages = parse_liste(content)?;
+        for name_type in all::<NameType>() {
+            if let Some(content) = files.get(name_type.language_filename().as_str()) {
+                let languages = parse_liste(content.to_string())?;
                 map.insert(name_type, languages);
             }
         }
@@ -160,6 +158,15 @@ impl TryFrom<&PathBuf> for Languages {
     }
 }
 
+impl TryFrom<&PathBuf> for Languages {
+    type Error = PhoneticError;
+
+    fn try_from(directory: &PathBuf) -> Result<Self, Self::Error> {
+        let files = read_directory(directory)?;
+        Self::try_from(&files_view(&files))
+    }
+}
+
 fn parse_liste(list: String) -> Result<BTreeSet<String>, PhoneticError> {
     let mut result = BTreeSet::new();
     let mut remains = list.as_str();
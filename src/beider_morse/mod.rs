@@ -1,7 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use either::Either;
@@ -18,6 +19,8 @@ use crate::beider_morse::languages::Languages;
 use crate::beider_morse::rule::Rules;
 use crate::{Encoder, PhoneticError};
 
+#[cfg(feature = "bm_language_cache")]
+mod cache;
 mod engine;
 mod lang;
 mod languages;
@@ -98,6 +101,91 @@ impl IsMatch for Either<Regex, OptimizedRegex> {
     }
 }
 
+/// Serialize a [Regex] as its pattern. It is compiled again when deserializing.
+#[cfg(feature = "binary_bm")]
+mod serde_regex {
+    use regex::Regex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        regex: &Regex,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(regex.as_str())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Regex, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map_err(D::Error::custom)
+    }
+}
+
+/// Serialize a rule context, either a [Regex] or an [OptimizedRegex].
+#[cfg(feature = "binary_bm")]
+mod serde_context {
+    use either::Either;
+    use regex::Regex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::OptimizedRegex;
+
+    #[derive(Serialize, Deserialize)]
+    enum Context {
+        Regex(String),
+        Optimized(OptimizedRegex),
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        context: &Either<Regex, OptimizedRegex>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match context {
+            Either::Left(regex) => Context::Regex(regex.as_str().to_string()),
+            Either::Right(optimized) => Context::Optimized(optimized.clone()),
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Either<Regex, OptimizedRegex>, D::Error> {
+        match Context::deserialize(deserializer)? {
+            Context::Regex(pattern) => Regex::new(&pattern)
+                .map(Either::Left)
+                .map_err(D::Error::custom),
+            Context::Optimized(optimized) => Ok(Either::Right(optimized)),
+        }
+    }
+}
+
+/// Read all `.txt` files of `directory`, keyed by their name.
+fn read_directory(directory: &Path) -> Result<BTreeMap<String, String>, PhoneticError> {
+    let mut files = BTreeMap::new();
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().map(|ext| ext != "txt").unwrap_or(true) {
+            continue;
+        }
+        if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+            files.insert(name.to_string(), std::fs::read_to_string(&path)?);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Borrow files read by [read_directory].
+fn files_view(files: &BTreeMap<String, String>) -> BTreeMap<&str, &str> {
+    files
+        .iter()
+        .map(|(name, content)| (name.as_str(), content.as_str()))
+        .collect()
+}
+
 /// Supported type of names. Unless you are matching particular family name, use [generic variant](NameType#Generic)
 /// as it should work reasonably well for non-name words. The other variant are specifically tune for family name
 /// and may not work well for general text.
@@ -169,16 +257,35 @@ impl TryFrom<OsString> for NameType {
 ///
 /// If `embedded_bm` feature is enable, then there is a [Default] implementation
 /// that only support `any` and `common` languages rules for each variant of
-/// [NameType]. It is provided as a convenience but as files are embedded into
+/// [NameType]. With `embedded_bm_full` feature, it supports all commons-codec
+/// languages (eg. `italian`, `russian`...) and language guessing rules.
+/// It is provided as a convenience but as files are embedded into
 /// code, it can result in a significant increase of binary size. The preferred
 /// way is to construct a new [ConfigFiles] with a [path to files](ConfigFiles#new).
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "embedded_bm", derive(Default))]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 pub struct ConfigFiles {
     langs: Langs,
     rules: Rules,
 }
 
+#[cfg(feature = "embedded_bm")]
+impl Default for ConfigFiles {
+    #[cfg(not(feature = "embedded_bm_full"))]
+    fn default() -> Self {
+        Self {
+            langs: Langs::default(),
+            rules: Rules::default(),
+        }
+    }
+
+    #[cfg(feature = "embedded_bm_full")]
+    fn default() -> Self {
+        // Embedded files are commons-codec ones, they are valid
+        Self::from_sources(&rule::EMBEDDED_FILES).unwrap()
+    }
+}
+
 impl ConfigFiles {
     /// Construct a new [ConfigFiles].
     ///
@@ -189,13 +296,146 @@ impl ConfigFiles {
     ///
     /// # Errors :
     /// Returns a [BMError] if it misses some files or some rules are not well-formed.
+    // Keep `&PathBuf` to not break the API
+    #[allow(clippy::ptr_arg)]
     pub fn new(directory: &PathBuf) -> Result<Self, PhoneticError> {
-        let languages = Languages::try_from(directory)?;
-        let langs = Langs::new(directory, &languages)?;
-        let rules = Rules::new(directory, &languages)?;
+        let files = read_directory(directory)?;
+        Self::build(&files_view(&files), Some(directory))
+    }
+
+    /// Construct a new [ConfigFiles] from in-memory files, for instance when rules
+    /// are embedded into your binary or come from an archive.
+    ///
+    /// # Parameter :
+    /// * `files` : content of each file, keyed by its name, as it would be found in
+    ///   the directory given to [new](ConfigFiles::new) : `<name_type>_languages.txt`
+    ///   for languages, `<name_type>_lang.txt` for language detection and
+    ///   `<name_type>_<rule_type>_<language>.txt` for rules (`rules`, `approx` or `exact`).
+    ///   Included files are also resolved from `files`.
+    ///
+    /// # Errors :
+    /// Returns a [BMError] if it misses some files or some rules are not well-formed.
+    ///
+    /// # Example :
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::collections::BTreeMap;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder};
+    ///
+    /// let mut files = BTreeMap::new();
+    /// for name_type in ["ash", "gen", "sep"] {
+    ///     files.insert(format!("{name_type}_languages.txt"), "any".to_string());
+    ///     files.insert(format!("{name_type}_lang.txt"), String::new());
+    ///     for rule_type in ["rules", "approx", "exact"] {
+    ///         files.insert(format!("{name_type}_{rule_type}_any.txt"), String::new());
+    ///     }
+    ///     for rule_type in ["approx", "exact"] {
+    ///         files.insert(format!("{name_type}_{rule_type}_common.txt"), String::new());
+    ///     }
+    /// }
+    /// let rules = r#"
+    /// "ph" "" "" "f"
+    /// "i" "" "" "i"
+    /// "l" "" "" "l"
+    /// "#;
+    /// files.insert("gen_rules_any.txt".to_string(), rules.to_string());
+    ///
+    /// let files = files.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    /// let config_files = ConfigFiles::from_sources(&files)?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files).build();
+    ///
+    /// assert_eq!(beider_morse.encode("phil"), "fil");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn from_sources(files: &BTreeMap<&str, &str>) -> Result<Self, PhoneticError> {
+        Self::build(files, None)
+    }
+
+    /// Parse `files`, read from `directory` if any.
+    fn build(
+        files: &BTreeMap<&str, &str>,
+        directory: Option<&Path>,
+    ) -> Result<Self, PhoneticError> {
+        let languages = Languages::try_from(files)?;
+        let langs = Langs::new(files, &languages, directory)?;
+        let rules = Rules::new(files, &languages)?;
 
         Ok(Self { langs, rules })
     }
+
+    /// Serialize this [ConfigFiles] into a compact binary format ([bincode](https://docs.rs/bincode)),
+    /// so rules can be parsed once and loaded with [from_bytes](ConfigFiles::from_bytes).
+    ///
+    /// With `binary_bm` feature, [ConfigFiles] also implements [Serialize], so any other
+    /// `serde` format can be used.
+    ///
+    /// # Example :
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let bytes = config_files.to_bytes();
+    ///
+    /// let loaded = ConfigFiles::from_bytes(&bytes)?;
+    /// assert_eq!(
+    ///     BeiderMorseBuilder::new(&loaded).build().encode("Angelo"),
+    ///     BeiderMorseBuilder::new(&config_files).build().encode("Angelo")
+    /// );
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "binary_bm")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Writing into a Vec can't fail and every field is serializable
+        bincode::serialize(self).unwrap()
+    }
+
+    /// Deserialize a [ConfigFiles] serialized with [to_bytes](ConfigFiles::to_bytes).
+    ///
+    /// # Parameter :
+    /// * `bytes` : the serialized [ConfigFiles].
+    ///
+    /// # Errors :
+    /// Returns a [BMError::ParseConfiguration] if `bytes` is not a valid serialized [ConfigFiles].
+    #[cfg(feature = "binary_bm")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BMError> {
+        bincode::deserialize(bytes).map_err(|error| BMError::ParseConfiguration(error.to_string()))
+    }
+
+    /// Replace the rules of a language, without reloading the other files. Only
+    /// the main rules of the language (those from file `<name_type>_rules_<language>.txt`)
+    /// are replaced, final rules are kept.
+    ///
+    /// Encoders built before the reload are not affected, as they borrow
+    /// this [ConfigFiles].
+    ///
+    /// # Parameters :
+    /// * `name_type` : the [NameType] of the rules.
+    /// * `language` : the language of the rules, it must be an existing language.
+    /// * `rules_text` : the content of the new rules file. If it includes other rules files,
+    ///   they are resolved from embedded rules (see `embedded_bm` feature).
+    ///
+    /// # Errors :
+    /// Returns a [BMError] if the language is unknown or rules are not well-formed. In that
+    /// case current rules are kept.
+    pub fn reload_language(
+        &mut self,
+        name_type: NameType,
+        language: &str,
+        rules_text: &str,
+    ) -> Result<(), BMError> {
+        self.rules
+            .reload(name_type, language, rules_text)
+            .map_err(|error| match error {
+                PhoneticError::BMError(error) => error,
+                error => BMError::BadRule(error.to_string()),
+            })
+    }
 }
 
 /// This is the Beider-Morse encoder.
@@ -275,12 +515,309 @@ impl BeiderMorse<'_> {
     pub fn encode_with_languages(&self, value: &str, languages: &LanguageSet) -> String {
         self.engine.encode_with_language_set(value, languages)
     }
+
+    /// Encode a value both with and without [concatenation](BeiderMorseBuilder::concat),
+    /// whatever the encoder was built with. Rules are shared, so it avoids building
+    /// two encoders.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Return
+    ///
+    /// A tuple that contains the concatenated encoding first and then the
+    /// non-concatenated one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, NameType};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .name_type(NameType::Ashkenazi)
+    ///     .build();
+    ///
+    /// let (concat, not_concat) = beider_morse.encode_both_concat("van helsing");
+    /// assert_ne!(concat, not_concat);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn encode_both_concat(&self, value: &str) -> (String, String) {
+        let mut engine = self.engine.clone();
+        engine.concat = true;
+        let concat = engine.encode(value);
+        engine.concat = false;
+        let not_concat = engine.encode(value);
+
+        (concat, not_concat)
+    }
+
+    /// Encode a value and return the number of hyphen-separated blocks of the encoding.
+    /// It is `1` for a simple name, `2` for a name with a possible prefix (eg. `d'ortley`
+    /// encodes to `(ortlaj|ortlej)-(dortlaj|dortlej)`), and more for multiple words
+    /// that are not [concatenated](BeiderMorseBuilder::concat). Nested blocks are not counted.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Return
+    ///
+    /// The number of blocks, `0` if encoding is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files).build();
+    ///
+    /// assert_eq!(beider_morse.block_count("Angelo"), 1);
+    /// assert_eq!(beider_morse.block_count("d'ortley"), 2);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn block_count(&self, value: &str) -> usize {
+        let encoded = self.engine.encode(value);
+        if encoded.is_empty() {
+            return 0;
+        }
+
+        split_top_level(&encoded, '-').len()
+    }
+
+    /// Encode a value and return each phonetic alternative, instead of joining them
+    /// with `|`. Alternatives of every block (see [encode_to_blocks](BeiderMorse::encode_to_blocks))
+    /// are returned one after the other, including nested blocks.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Return
+    ///
+    /// Phonetic alternatives, in the same order as in [encode](Encoder::encode).
+    /// It is empty if encoding is empty; words without encoding are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, RuleType};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .rule_type(RuleType::Exact)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     beider_morse.encode_to_phonemes("d'ortley"),
+    ///     vec!["ortlaj", "ortlej", "dortlaj", "dortlej"]
+    /// );
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn encode_to_phonemes(&self, value: &str) -> Vec<String> {
+        split_phonemes(&self.engine.encode(value))
+    }
+
+    /// Same as [encode_to_phonemes](BeiderMorse::encode_to_phonemes) but with the provided
+    /// [LanguageSet], see [encode_with_languages](BeiderMorse::encode_with_languages).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` : value to encode.
+    /// * `languages` : languages to use.
+    ///
+    /// # Return
+    ///
+    /// Phonetic alternatives, in the same order as in [encode_with_languages](BeiderMorse::encode_with_languages).
+    pub fn encode_to_phonemes_with_languages(
+        &self,
+        value: &str,
+        languages: &LanguageSet,
+    ) -> Vec<String> {
+        split_phonemes(&self.engine.encode_with_language_set(value, languages))
+    }
+
+    /// Encode a value and return its hyphen-separated blocks (see [block_count](BeiderMorse::block_count)),
+    /// each one with its phonetic alternatives. Nested blocks are kept as a single
+    /// alternative, as in [encode](Encoder::encode).
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Return
+    ///
+    /// The blocks, each one contains its phonetic alternatives.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, RuleType};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .rule_type(RuleType::Exact)
+    ///     .build();
+    ///
+    /// // Without and with the prefix
+    /// assert_eq!(
+    ///     beider_morse.encode_to_blocks("d'ortley"),
+    ///     vec![vec!["ortlaj", "ortlej"], vec!["dortlaj", "dortlej"]]
+    /// );
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn encode_to_blocks(&self, value: &str) -> Vec<Vec<String>> {
+        split_blocks(&self.engine.encode(value))
+    }
+
+    /// Same as [encode_to_blocks](BeiderMorse::encode_to_blocks) but with the provided
+    /// [LanguageSet], see [encode_with_languages](BeiderMorse::encode_with_languages).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` : value to encode.
+    /// * `languages` : languages to use.
+    ///
+    /// # Return
+    ///
+    /// The blocks, each one contains its phonetic alternatives.
+    pub fn encode_to_blocks_with_languages(
+        &self,
+        value: &str,
+        languages: &LanguageSet,
+    ) -> Vec<Vec<String>> {
+        split_blocks(&self.engine.encode_with_language_set(value, languages))
+    }
+}
+
+/// Split `value` on `separator` that are not between parentheses.
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if ch == separator && depth == 0 => {
+                result.push(&value[start..index]);
+                start = index + ch.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    result.push(&value[start..]);
+
+    result
+}
+
+/// Split an encoding into blocks of alternatives.
+fn split_blocks(encoded: &str) -> Vec<Vec<String>> {
+    if encoded.is_empty() {
+        return Vec::new();
+    }
+
+    split_top_level(encoded, '-')
+        .into_iter()
+        .map(|block| {
+            let block = match block.strip_prefix('(').and_then(|b| b.strip_suffix(')')) {
+                // Only remove parentheses that enclose the whole block
+                Some(inner) if split_top_level(block, '-').len() == 1 && is_balanced(inner) => {
+                    inner
+                }
+                _ => block,
+            };
+            split_top_level(block, '|')
+                .into_iter()
+                .map(|phoneme| phoneme.to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// Check that parentheses of `value` are balanced, without closing more than opened.
+fn is_balanced(value: &str) -> bool {
+    let mut depth = 0;
+    for ch in value.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' if depth == 0 => return false,
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+
+    depth == 0
+}
+
+/// Split an encoding into phonemes, recursively for nested blocks. Empty
+/// phonemes (eg. a word without encoding) are skipped.
+fn split_phonemes(encoded: &str) -> Vec<String> {
+    split_blocks(encoded)
+        .into_iter()
+        .flatten()
+        .filter(|phoneme| !phoneme.is_empty())
+        .flat_map(|phoneme| {
+            if phoneme.contains('(') {
+                split_phonemes(&phoneme)
+            } else {
+                vec![phoneme]
+            }
+        })
+        .collect()
 }
 
 impl Encoder for BeiderMorse<'_> {
     fn encode(&self, value: &str) -> String {
         self.engine.encode(value)
     }
+
+    /// Check that two strings have at least one alternative in common. Codes of
+    /// multiple words, like `(ortlaj|ortlej)-(dortlaj|dortlej)`, are split on
+    /// parentheses and `-` too, so each alternative of each word is compared.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder, RuleType};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .rule_type(RuleType::Exact)
+    ///     .build();
+    ///
+    /// assert!(beider_morse.is_encoded_equals_any("D'Ortley", "Ortley"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn is_encoded_equals_any(&self, first: &str, second: &str) -> bool {
+        fn alternatives(code: &str) -> BTreeSet<&str> {
+            code.split(['|', '-', '(', ')'])
+                .filter(|alternative| !alternative.is_empty())
+                .collect()
+        }
+
+        let first = self.encode(first);
+        let second = self.encode(second);
+
+        !alternatives(&first).is_disjoint(&alternatives(&second))
+    }
 }
 
 /// This is a builder to construct a [BeiderMorse] encoder.
@@ -293,6 +830,10 @@ pub struct BeiderMorseBuilder<'a> {
     rule_type: RuleType,
     concat: bool,
     max_phonemes: usize,
+    max_input_length: Option<usize>,
+    max_branches: Option<usize>,
+    #[cfg(feature = "bm_language_cache")]
+    language_cache: Option<usize>,
 }
 
 impl<'a> BeiderMorseBuilder<'a> {
@@ -308,6 +849,10 @@ impl<'a> BeiderMorseBuilder<'a> {
             rule_type: RuleType::Approx,
             concat: true,
             max_phonemes: DEFAULT_MAX_PHONEMES,
+            max_input_length: None,
+            max_branches: None,
+            #[cfg(feature = "bm_language_cache")]
+            language_cache: None,
         }
     }
 
@@ -337,6 +882,101 @@ impl<'a> BeiderMorseBuilder<'a> {
         self
     }
 
+    /// Set the maximum number of characters of a value. Longer values
+    /// are not encoded, and an empty string is returned instead. This protects
+    /// against inputs that would take too long to encode.
+    ///
+    /// By default, there is no maximum.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .max_input_length(6)
+    ///     .build();
+    ///
+    /// assert!(!beider_morse.encode("Angelo").is_empty());
+    /// assert_eq!(beider_morse.encode("Angelopoulos"), "");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn max_input_length(mut self, max_input_length: usize) -> Self {
+        self.max_input_length = Some(max_input_length);
+        self
+    }
+
+    /// Set the maximum number of alternatives of an encoding, including those
+    /// produced by prefixes and multiple words. This gives an upper bound to the
+    /// output for untrusted inputs, unlike [max_phonemes](BeiderMorseBuilder::max_phonemes)
+    /// that only applies to each word.
+    ///
+    /// When there are more alternatives, only the lexicographically smallest ones
+    /// are kept and blocks are flattened into a single `|` separated list.
+    ///
+    /// By default, there is no maximum.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `max_branches` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder, RuleType};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .rule_type(RuleType::Exact)
+    ///     .max_branches(3)
+    ///     .build();
+    ///
+    /// assert_eq!(beider_morse.encode("d'ortley"), "dortlaj|dortlej|ortlaj");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn max_branches(mut self, max_branches: usize) -> Self {
+        assert!(max_branches > 0, "max_branches must be greater than 0");
+        self.max_branches = Some(max_branches);
+        self
+    }
+
+    /// Cache guessed languages of, at most, `capacity` values, so encoding the same
+    /// value again (case is ignored) doesn't guess its languages again. When the cache
+    /// is full, the least recently used value is removed.
+    ///
+    /// The cache is shared by clones of the encoder, and it is thread-safe.
+    ///
+    /// By default, there is no cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, Encoder};
+    ///
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .language_cache(1000)
+    ///     .build();
+    ///
+    /// assert_eq!(beider_morse.encode("Angelo"), beider_morse.encode("angelo"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bm_language_cache")]
+    pub fn language_cache(mut self, capacity: usize) -> Self {
+        self.language_cache = Some(capacity);
+        self
+    }
+
     /// Build a new [BeiderMorse] encoder.
     pub fn build(&self) -> BeiderMorse<'a> {
         let lang = self.config_files.langs.get(&self.name_type).unwrap();
@@ -348,6 +988,10 @@ impl<'a> BeiderMorseBuilder<'a> {
             rule_type: self.rule_type.into(),
             concat: self.concat,
             max_phonemes: self.max_phonemes,
+            max_input_length: self.max_input_length,
+            max_branches: self.max_branches,
+            #[cfg(feature = "bm_language_cache")]
+            language_cache: self.language_cache.map(cache::LanguageCache::new),
         };
         BeiderMorse { engine }
     }
@@ -364,6 +1008,50 @@ mod tests {
             ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/")).unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "bm_language_cache")]
+    fn test_language_cache() {
+        let without_cache = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .language_cache(16)
+            .build();
+        let cache = encoder.engine.language_cache.as_ref().unwrap();
+
+        for name in ["Angelo", "d'ortley", "abram brown"] {
+            let expected = without_cache.encode(name);
+            for _ in 0..50 {
+                assert_eq!(encoder.encode(name), expected, "Error for {name}");
+            }
+        }
+        let hits = cache.hits();
+        assert!(hits >= 3 * 49, "Only {hits} hits");
+
+        // Case is ignored, and clones share the cache
+        let clone = encoder.clone();
+        assert_eq!(clone.encode("ANGELO"), without_cache.encode("ANGELO"));
+        assert!(cache.hits() > hits);
+    }
+
+    #[test]
+    fn test_is_encoded_equals_any() {
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+
+        // "svYrts|svarts|svorts" and "svarts|svorts"
+        assert!(!encoder.is_encoded_equals("Schwarz", "Szwarc"));
+        assert!(encoder.is_encoded_equals_any("Schwarz", "Szwarc"));
+        assert!(encoder.is_encoded_equals_any("Andersen", "Anderson"));
+        assert!(!encoder.is_encoded_equals_any("Peter", "Petra"));
+        assert!(!encoder.is_encoded_equals_any("Schwarz", "Angelo"));
+
+        // "(ortlaj|ortlej)-(dortlaj|dortlej)" and "ortlaj|ortlej"
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .build();
+        assert!(encoder.is_encoded_equals_any("D'Ortley", "Ortley"));
+        assert!(encoder.is_encoded_equals_any("Ortley", "D'Ortley"));
+        assert!(!encoder.is_encoded_equals_any("D'Ortley", "Peter"));
+    }
+
     #[test]
     fn test_all_chars() -> Result<(), BMError> {
         let builder = BeiderMorseBuilder::new(&CONFIG_FILE);
@@ -376,6 +1064,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_name_type_from_str() {
+        for name_type in enum_iterator::all::<NameType>() {
+            assert_eq!(name_type.to_string().parse::<NameType>(), Ok(name_type));
+        }
+        assert_eq!("ash".parse::<NameType>(), Ok(NameType::Ashkenazi));
+        assert_eq!("gen".parse::<NameType>(), Ok(NameType::Generic));
+        assert_eq!("sep".parse::<NameType>(), Ok(NameType::Sephardic));
+        assert_eq!(
+            "klingon".parse::<NameType>(),
+            Err(BMError::UnknownNameType("klingon".to_string()))
+        );
+    }
+
     #[test]
     fn test_oom() -> Result<(), BMError> {
         let input = "200697900'-->&#1913348150;</  bceaeef >aadaabcf\"aedfbff<!--\'-->?>cae\
@@ -397,6 +1099,136 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_max_branches() -> Result<(), BMError> {
+        let input = "200697900'-->&#1913348150;</  bceaeef >aadaabcf\"aedfbff<!--\'-->?>cae\
+        cfaaa><?&#<!--</script>&lang&fc;aadeaf?>>&bdquo<    cc =\"abff\"    /></   afe  ><script>\
+        <!-- f(';<    cf aefbeef = \"bfabadcf\" ebbfeedd = fccabeb >";
+
+        for concat in [true, false] {
+            for max_branches in [1, 5, 20] {
+                let encoder = BeiderMorseBuilder::new(&CONFIG_FILE)
+                    .name_type(NameType::Generic)
+                    .rule_type(RuleType::Exact)
+                    .concat(concat)
+                    .max_branches(max_branches)
+                    .build();
+
+                let result = encoder.encode(input);
+                assert!(!result.is_empty());
+                assert!(encoder.encode_to_phonemes(input).len() <= max_branches);
+                assert!(result.split('|').count() <= max_branches);
+                // Deterministic
+                assert_eq!(result, encoder.encode(input));
+            }
+        }
+
+        // Not reached, left untouched
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .max_branches(4)
+            .build();
+        assert_eq!(
+            encoder.encode("d'ortley"),
+            "(ortlaj|ortlej)-(dortlaj|dortlej)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_branches_zero() {
+        let _ = BeiderMorseBuilder::new(&CONFIG_FILE).max_branches(0);
+    }
+
+    #[test]
+    fn test_encode_bounded() -> Result<(), BMError> {
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+
+        let code = encoder.encode("Van Helsing");
+        assert!(code.chars().count() > 64);
+
+        let bounded = encoder.encode_bounded("Van Helsing", 64);
+        assert_eq!(bounded.chars().count(), 64);
+        assert!(code.starts_with(&bounded));
+        assert_eq!(encoder.encode_bounded("Van Helsing", 1000), code);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_input_length() -> Result<(), BMError> {
+        let input = "bceaeef aadaabcf aedfbff ".repeat(1000);
+
+        let builder = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .max_input_length(64);
+        let encoder = builder.build();
+
+        assert_eq!(encoder.encode(&input), "");
+        assert_eq!(encoder.encode(&input), "");
+        assert_eq!(
+            encoder.encode_with_languages(&input, &LanguageSet::from(vec!["english"])),
+            ""
+        );
+
+        // Limit is inclusive and counts characters, not bytes
+        let input = "\u{00e9}".repeat(64);
+        assert_ne!(encoder.encode(&input), "");
+        assert_eq!(encoder.encode(&format!("{input}e")), "");
+        assert_eq!(
+            encoder.encode("Angelo"),
+            builder.max_input_length(6).build().encode("Angelo")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_language() -> Result<(), BMError> {
+        let mut config_files = CONFIG_FILE.clone();
+        let italian = LanguageSet::from(vec!["italian"]);
+
+        let encode = |config_files: &ConfigFiles| {
+            BeiderMorseBuilder::new(config_files)
+                .rule_type(RuleType::Exact)
+                .build()
+                .encode_with_languages("Angelo", &italian)
+        };
+        assert_eq!(encode(&config_files), "andZelo");
+
+        let rules = std::fs::read_to_string("./test_assets/cc-rules/gen_rules_italian.txt")?;
+        let rules = rules.replace("\"g\" \"\" \"[ei]\" \"dZ\"", "\"g\" \"\" \"[ei]\" \"Z\"");
+        config_files.reload_language(NameType::Generic, "italian", &rules)?;
+        assert_eq!(encode(&config_files), "anZelo");
+
+        // Other languages are untouched
+        let spanish = LanguageSet::from(vec!["spanish"]);
+        assert_eq!(
+            BeiderMorseBuilder::new(&config_files)
+                .rule_type(RuleType::Exact)
+                .build()
+                .encode_with_languages("Angelo", &spanish),
+            BeiderMorseBuilder::new(&CONFIG_FILE)
+                .rule_type(RuleType::Exact)
+                .build()
+                .encode_with_languages("Angelo", &spanish)
+        );
+
+        // Errors
+        assert!(config_files
+            .reload_language(NameType::Generic, "klingon", &rules)
+            .is_err());
+        assert!(matches!(
+            config_files.reload_language(NameType::Generic, "italian", "\"g\" \"\""),
+            Err(BMError::BadRule(_))
+        ));
+        assert_eq!(encode(&config_files), "anZelo");
+
+        Ok(())
+    }
+
     #[test]
     fn test_ascii_encode_not_empty_1_letter() -> Result<(), BMError> {
         let builder = BeiderMorseBuilder::new(&CONFIG_FILE);
@@ -513,6 +1345,172 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_both_concat() {
+        let builder = BeiderMorseBuilder::new(&CONFIG_FILE).name_type(NameType::Ashkenazi);
+        let concat = builder.clone().concat(true).build();
+        let not_concat = builder.concat(false).build();
+
+        let (result_concat, result_not_concat) = concat.encode_both_concat("van helsing");
+        assert_ne!(result_concat, result_not_concat);
+        assert_eq!(result_concat, concat.encode("van helsing"));
+        assert_eq!(result_not_concat, not_concat.encode("van helsing"));
+
+        assert_eq!(
+            not_concat.encode_both_concat("van helsing"),
+            (result_concat, result_not_concat)
+        );
+    }
+
+    #[test]
+    fn test_from_sources() -> Result<(), PhoneticError> {
+        let mut files = BTreeMap::new();
+        for name_type in ["ash", "gen", "sep"] {
+            files.insert(format!("{name_type}_languages.txt"), "any".to_string());
+            files.insert(format!("{name_type}_lang.txt"), String::new());
+            for rule_type in ["approx", "exact"] {
+                files.insert(format!("{name_type}_{rule_type}_any.txt"), String::new());
+                files.insert(format!("{name_type}_{rule_type}_common.txt"), String::new());
+            }
+            let rules = format!("#include {name_type}_included\n\"a\" \"\" \"\" \"o\"");
+            files.insert(format!("{name_type}_rules_any.txt"), rules);
+            files.insert(
+                format!("{name_type}_included.txt"),
+                "\"b\" \"\" \"\" \"(p|v)\"".to_string(),
+            );
+        }
+        let view = files_view(&files);
+        let config_files = ConfigFiles::from_sources(&view)?;
+        let beider_morse = BeiderMorseBuilder::new(&config_files).build();
+        assert_eq!(beider_morse.encode("aba"), "opo|ovo");
+
+        // Same as reading the directory
+        let files = read_directory(Path::new("./test_assets/cc-rules/"))?;
+        let config_files = ConfigFiles::from_sources(&files_view(&files))?;
+        let from_sources = BeiderMorseBuilder::new(&config_files).build();
+        let from_directory = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+        for name in ["Angelo", "Renault", "d'ortley", "Van Helsing"] {
+            assert_eq!(from_sources.encode(name), from_directory.encode(name));
+        }
+
+        // Missing files
+        let mut missing = view.clone();
+        missing.remove("gen_lang.txt");
+        assert!(ConfigFiles::from_sources(&missing).is_err());
+        let mut missing = view.clone();
+        missing.remove("sep_included.txt");
+        assert!(ConfigFiles::from_sources(&missing).is_err());
+        let mut missing = view;
+        missing.remove("ash_languages.txt");
+        assert!(ConfigFiles::from_sources(&missing).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "binary_bm")]
+    #[test]
+    fn test_to_bytes() -> Result<(), BMError> {
+        let bytes = CONFIG_FILE.to_bytes();
+        let config_files = ConfigFiles::from_bytes(&bytes)?;
+
+        for rule_type in [RuleType::Approx, RuleType::Exact] {
+            let original = BeiderMorseBuilder::new(&CONFIG_FILE)
+                .rule_type(rule_type)
+                .build();
+            let loaded = BeiderMorseBuilder::new(&config_files)
+                .rule_type(rule_type)
+                .build();
+            assert_eq!(loaded.encode("Van Helsing"), original.encode("Van Helsing"));
+            assert_eq!(loaded.encode("Angelo"), original.encode("Angelo"));
+        }
+
+        assert!(ConfigFiles::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        assert!(ConfigFiles::from_bytes(b"not a config").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_singleton_language_set() {
+        let italian = LanguageSet::from(vec!["italian"]);
+
+        let approx = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+        assert_eq!(
+            approx.encode_with_languages("Angelo", &italian),
+            "anzilo|onzilo"
+        );
+        let exact = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .build();
+        assert_eq!(exact.encode_with_languages("Angelo", &italian), "andZelo");
+    }
+
+    #[test]
+    fn test_encode_to_phonemes() {
+        let encoder = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .build();
+
+        let encoded = encoder.encode("d'ortley");
+        let blocks = encoder.encode_to_blocks("d'ortley");
+        let joined: Vec<String> = blocks
+            .iter()
+            .map(|block| format!("({})", block.join("|")))
+            .collect();
+        assert_eq!(joined.join("-"), encoded);
+        assert_eq!(
+            encoder.encode_to_phonemes("d'ortley"),
+            vec!["ortlaj", "ortlej", "dortlaj", "dortlej"]
+        );
+
+        let encoded = encoder.encode("Renault");
+        let phonemes = encoder.encode_to_phonemes("Renault");
+        assert_eq!(phonemes.join("|"), encoded);
+        assert_eq!(encoder.encode_to_blocks("Renault"), vec![phonemes]);
+
+        let italian = LanguageSet::from(vec!["italian"]);
+        let phonemes = encoder.encode_to_phonemes_with_languages("Angelo", &italian);
+        assert_eq!(phonemes, vec!["andZelo"]);
+        assert_eq!(
+            encoder.encode_to_blocks_with_languages("Angelo", &italian),
+            vec![phonemes]
+        );
+
+        assert!(encoder.encode_to_phonemes("").is_empty());
+        assert!(encoder.encode_to_blocks("").is_empty());
+    }
+
+    #[test]
+    fn test_split_blocks() {
+        assert_eq!(
+            split_blocks("((a|b)-(c))-(d|e)"),
+            vec![vec!["(a|b)-(c)"], vec!["d", "e"]]
+        );
+        assert_eq!(
+            split_phonemes("((a|b)-(c))-(d|e)"),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(split_blocks("(a)-b|c"), vec![vec!["a"], vec!["b", "c"]]);
+    }
+
+    #[test]
+    fn test_block_count() {
+        let beider_morse = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .rule_type(RuleType::Exact)
+            .build();
+        assert_eq!(beider_morse.block_count("Angelo"), 1);
+        assert_eq!(beider_morse.block_count("d'ortley"), 2);
+        assert_eq!(beider_morse.block_count("van helsing"), 2);
+        assert_eq!(beider_morse.block_count(""), 0);
+
+        let beider_morse = BeiderMorseBuilder::new(&CONFIG_FILE)
+            .name_type(NameType::Ashkenazi)
+            .concat(false)
+            .build();
+        assert_eq!(beider_morse.block_count("abram brown custer"), 3);
+        assert_eq!(beider_morse.block_count("abram"), 1);
+    }
+
     #[test]
     #[cfg(feature = "embedded_bm")]
     /// Basic test checking that it doesn't fail
@@ -532,6 +1530,35 @@ mod tests {
         assert_eq!(rules.unwrap().len(), 22);
     }
 
+    #[test]
+    #[cfg(feature = "embedded_bm_full")]
+    fn test_config_file_default_full() {
+        let config_file = ConfigFiles::default();
+
+        let rules = config_file
+            .rules
+            .rules(NameType::Generic, PrivateRuleType::Rules, "italian");
+        assert!(rules.is_some());
+
+        let italian = LanguageSet::from(vec!["italian"]);
+        let beider_morse = BeiderMorseBuilder::new(&config_file).build();
+        let expected = BeiderMorseBuilder::new(&CONFIG_FILE).build();
+        for name in ["Angelo", "Giuseppe", "Schwarz"] {
+            assert_eq!(beider_morse.encode(name), expected.encode(name));
+        }
+        // Italian rules read "ge" as "dZe"
+        assert_eq!(
+            beider_morse.encode_with_languages("Angelo", &italian),
+            "anzilo|onzilo"
+        );
+        // Language guessing restricts "Giuseppe" to a few languages
+        assert_eq!(beider_morse.encode("Giuseppe"), "iusipi|zusipi|zuzipi");
+        assert_ne!(
+            beider_morse.encode("Giuseppe"),
+            beider_morse.encode_with_languages("Giuseppe", &LanguageSet::Any)
+        );
+    }
+
     #[test]
     fn test_builder() {
         let builder = BeiderMorseBuilder::new(&CONFIG_FILE);
@@ -539,7 +1566,9 @@ mod tests {
         assert_eq!(builder.rule_type, RuleType::Approx);
         assert_eq!(builder.name_type, NameType::Generic);
         assert!(builder.concat);
+        assert_eq!(builder.max_branches, None);
         assert_eq!(builder.max_phonemes, DEFAULT_MAX_PHONEMES);
+        assert_eq!(builder.max_input_length, None);
 
         let builder = builder.concat(false);
 
@@ -1,7 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::btree_map::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
-use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use either::Either;
@@ -14,8 +13,8 @@ use crate::beider_morse::regex_optim::OptimizedRegex;
 use crate::beider_morse::Languages;
 use crate::helper::CharSequence;
 use crate::{
-    build_error, end_of_line, include, multiline_comment, quadruplet, BMError, NameType,
-    PhoneticError,
+    build_error, end_of_line, include, multiline_comment, quadruplet, quadruplet_error, BMError,
+    NameType, PhoneticError,
 };
 
 const APPROX: &str = "approx";
@@ -42,6 +41,24 @@ pub enum PrivateRuleType {
     Rules,
 }
 
+impl Display for RuleType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", PrivateRuleType::from(*self))
+    }
+}
+
+impl FromStr for RuleType {
+    type Err = BMError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            APPROX => Ok(Self::Approx),
+            EXACT => Ok(Self::Exact),
+            other => Err(BMError::UnknownRuleType(other.to_string())),
+        }
+    }
+}
+
 impl From<RuleType> for PrivateRuleType {
     fn from(rule_type: RuleType) -> Self {
         match rule_type {
@@ -215,8 +232,16 @@ fn parse_rule(
     filename: &str,
 ) -> Result<BTreeMap<char, Vec<Rule>>, PhoneticError> {
     let content = resolver.resolve(filename)?;
+    parse_rule_content(resolver, filename, &content)
+}
+
+fn parse_rule_content(
+    resolver: &Resolver,
+    filename: &str,
+    content: &str,
+) -> Result<BTreeMap<char, Vec<Rule>>, PhoneticError> {
     let mut result: BTreeMap<char, Vec<Rule>> = BTreeMap::new();
-    let mut remains = content.as_str();
+    let mut remains = content;
     let mut line_number: usize = 0;
 
     while !remains.is_empty() {
@@ -241,7 +266,12 @@ fn parse_rule(
                     Ok(optimized) => Either::Right(optimized.clone()),
                     Err(_) => Either::Left(Regex::new(&right_context)?),
                 };
-            let phoneme = parse_phoneme_expr(phoneme_expr)?;
+            let phoneme = parse_phoneme_expr(phoneme_expr).map_err(|error| match error {
+                BMError::WrongPhoneme(message) => {
+                    BMError::WrongPhoneme(format!("{filename}:{line_number} {message}"))
+                }
+                other => other,
+            })?;
             let rule = Rule {
                 location: filename.to_string(),
                 line: line_number,
@@ -295,7 +325,7 @@ fn parse_rule(
             line_number,
             Some(filename.to_string()),
             remains,
-            "Can't parse line".to_string(),
+            quadruplet_error(remains).unwrap_or_else(|| "Can't parse line".to_string()),
         ));
     }
 
@@ -327,19 +357,19 @@ fn build_rules(resolver: Resolver, languages: &Languages) -> Result<Rules, Phone
     Ok(Rules { rules })
 }
 
-struct Resolver {
-    path: Option<PathBuf>,
+struct Resolver<'a> {
+    files: Option<&'a BTreeMap<&'a str, &'a str>>,
 }
 
-impl Resolver {
+impl Resolver<'_> {
     fn resolve(&self, filename: &str) -> Result<String, BMError> {
-        match &self.path {
-            Some(folder) => {
-                let f = folder.join(format!("{filename}.txt"));
-                std::fs::read_to_string(f).map_err(|_| {
+        match self.files {
+            Some(files) => files
+                .get(format!("{filename}.txt").as_str())
+                .map(|v| v.to_string())
+                .ok_or_else(|| {
                     BMError::WrongFilename(format!("Can't find file for {filename} rules"))
-                })
-            }
+                }),
             #[cfg(feature = "embedded_bm")]
             None => embedded::EMBEDDED_RULES
                 .get(filename)
@@ -356,12 +386,21 @@ impl Resolver {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 pub(crate) struct Rule {
     location: String,
     line: usize,
+    #[cfg_attr(
+        feature = "binary_bm",
+        serde(with = "crate::beider_morse::serde_context")
+    )]
     left_context: Either<Regex, OptimizedRegex>,
     pattern: String,
     pattern_length_char: usize,
+    #[cfg_attr(
+        feature = "binary_bm",
+        serde(with = "crate::beider_morse::serde_context")
+    )]
     right_context: Either<Regex, OptimizedRegex>,
     phoneme: PhonemeList,
 }
@@ -408,6 +447,7 @@ impl Display for Rule {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "binary_bm", derive(Serialize, Deserialize))]
 pub(crate) struct Rules {
     rules: BTreeMap<(NameType, PrivateRuleType, String), BTreeMap<char, Vec<Rule>>>,
 }
@@ -423,16 +463,42 @@ impl Rules {
             .get(&(name_type, rule_type, language.to_string()))
     }
 
-    pub fn new(rules_folder: &Path, languages: &Languages) -> Result<Self, PhoneticError> {
-        let resolver = Resolver {
-            path: Some(rules_folder.to_path_buf()),
-        };
+    pub fn new(files: &BTreeMap<&str, &str>, languages: &Languages) -> Result<Self, PhoneticError> {
+        let resolver = Resolver { files: Some(files) };
         build_rules(resolver, languages)
     }
+
+    /// Replace the main rules of `language` by the ones parsed from `rules_text`.
+    /// Includes are resolved with embedded rules.
+    pub fn reload(
+        &mut self,
+        name_type: NameType,
+        language: &str,
+        rules_text: &str,
+    ) -> Result<(), PhoneticError> {
+        let key = (name_type, PrivateRuleType::Rules, language.to_string());
+        let filename = format!("{name_type}_{}_{language}", PrivateRuleType::Rules);
+        if !self.rules.contains_key(&key) {
+            return Err(BMError::WrongFilename(format!(
+                "Unknown language {language} for {name_type} rules"
+            ))
+            .into());
+        }
+
+        let resolver = Resolver { files: None };
+        let rules = parse_rule_content(&resolver, &filename, rules_text)?;
+        self.rules.insert(key, rules);
+
+        Ok(())
+    }
 }
 
+#[cfg(feature = "embedded_bm_full")]
+pub(crate) use embedded::EMBEDDED_FILES;
+
 /// Module that contains default rules (any and commons) and [Default] implementation
-/// for [Rules] for convenience with features.
+/// for [Rules] for convenience with features. With `embedded_bm_full`, it also contains
+/// all other files.
 #[cfg(feature = "embedded_bm")]
 mod embedded {
     use std::collections::BTreeMap;
@@ -486,9 +552,155 @@ mod embedded {
         ]);
     }
 
+    /// Embed rules files, keyed by their file name.
+    #[cfg(feature = "embedded_bm_full")]
+    macro_rules! embed_files {
+        ($($name:literal),* $(,)?) => {
+            BTreeMap::from([$(
+                (
+                    concat!($name, ".txt"),
+                    include_str!(concat!("../../rules/bm/", $name, ".txt")),
+                ),
+            )*])
+        };
+    }
+
+    #[cfg(feature = "embedded_bm_full")]
+    lazy_static! {
+        /// All commons-codec files, languages included, to feed [ConfigFiles::from_sources](crate::ConfigFiles::from_sources).
+        pub static ref EMBEDDED_FILES: BTreeMap<&'static str, &'static str> = embed_files![
+            "ash_approx_any",
+            "ash_approx_common",
+            "ash_approx_cyrillic",
+            "ash_approx_english",
+            "ash_approx_french",
+            "ash_approx_german",
+            "ash_approx_hebrew",
+            "ash_approx_hungarian",
+            "ash_approx_polish",
+            "ash_approx_romanian",
+            "ash_approx_russian",
+            "ash_approx_spanish",
+            "ash_exact_any",
+            "ash_exact_approx_common",
+            "ash_exact_common",
+            "ash_exact_cyrillic",
+            "ash_exact_english",
+            "ash_exact_french",
+            "ash_exact_german",
+            "ash_exact_hebrew",
+            "ash_exact_hungarian",
+            "ash_exact_polish",
+            "ash_exact_romanian",
+            "ash_exact_russian",
+            "ash_exact_spanish",
+            "ash_hebrew_common",
+            "ash_lang",
+            "ash_languages",
+            "ash_rules_any",
+            "ash_rules_cyrillic",
+            "ash_rules_english",
+            "ash_rules_french",
+            "ash_rules_german",
+            "ash_rules_hebrew",
+            "ash_rules_hungarian",
+            "ash_rules_polish",
+            "ash_rules_romanian",
+            "ash_rules_russian",
+            "ash_rules_spanish",
+            "gen_approx_any",
+            "gen_approx_arabic",
+            "gen_approx_common",
+            "gen_approx_cyrillic",
+            "gen_approx_czech",
+            "gen_approx_dutch",
+            "gen_approx_english",
+            "gen_approx_french",
+            "gen_approx_german",
+            "gen_approx_greek",
+            "gen_approx_greeklatin",
+            "gen_approx_hebrew",
+            "gen_approx_hungarian",
+            "gen_approx_italian",
+            "gen_approx_polish",
+            "gen_approx_portuguese",
+            "gen_approx_romanian",
+            "gen_approx_russian",
+            "gen_approx_spanish",
+            "gen_approx_turkish",
+            "gen_exact_any",
+            "gen_exact_approx_common",
+            "gen_exact_arabic",
+            "gen_exact_common",
+            "gen_exact_cyrillic",
+            "gen_exact_czech",
+            "gen_exact_dutch",
+            "gen_exact_english",
+            "gen_exact_french",
+            "gen_exact_german",
+            "gen_exact_greek",
+            "gen_exact_greeklatin",
+            "gen_exact_hebrew",
+            "gen_exact_hungarian",
+            "gen_exact_italian",
+            "gen_exact_polish",
+            "gen_exact_portuguese",
+            "gen_exact_romanian",
+            "gen_exact_russian",
+            "gen_exact_spanish",
+            "gen_exact_turkish",
+            "gen_hebrew_common",
+            "gen_lang",
+            "gen_languages",
+            "gen_rules_any",
+            "gen_rules_arabic",
+            "gen_rules_cyrillic",
+            "gen_rules_czech",
+            "gen_rules_dutch",
+            "gen_rules_english",
+            "gen_rules_french",
+            "gen_rules_german",
+            "gen_rules_greek",
+            "gen_rules_greeklatin",
+            "gen_rules_hebrew",
+            "gen_rules_hungarian",
+            "gen_rules_italian",
+            "gen_rules_polish",
+            "gen_rules_portuguese",
+            "gen_rules_romanian",
+            "gen_rules_russian",
+            "gen_rules_spanish",
+            "gen_rules_turkish",
+            "sep_approx_any",
+            "sep_approx_common",
+            "sep_approx_french",
+            "sep_approx_hebrew",
+            "sep_approx_italian",
+            "sep_approx_portuguese",
+            "sep_approx_spanish",
+            "sep_exact_any",
+            "sep_exact_approx_common",
+            "sep_exact_common",
+            "sep_exact_french",
+            "sep_exact_hebrew",
+            "sep_exact_italian",
+            "sep_exact_portuguese",
+            "sep_exact_spanish",
+            "sep_hebrew_common",
+            "sep_lang",
+            "sep_languages",
+            "sep_rules_any",
+            "sep_rules_french",
+            "sep_rules_hebrew",
+            "sep_rules_italian",
+            "sep_rules_portuguese",
+            "sep_rules_spanish",
+        ];
+    }
+
     impl Default for Rules {
         fn default() -> Self {
-            let resolver = Resolver { path: None };
+            let resolver = Resolver { files: None };
             build_rules(resolver, &Languages::default()).unwrap()
         }
     }
@@ -497,8 +709,10 @@ mod embedded {
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
+    use std::path::Path;
 
     use super::*;
+    use crate::beider_morse::{files_view, read_directory};
 
     fn make_phonemes() -> Vec<Vec<Phoneme>> {
         let mut result = Vec::new();
@@ -603,8 +817,9 @@ mod tests {
 
     #[test]
     fn test_with_path() -> Result<(), PhoneticError> {
-        let path = &PathBuf::from("./test_assets/cc-rules/");
-        let rules = Rules::new(path, &Languages::try_from(path)?)?;
+        let files = read_directory(Path::new("./test_assets/cc-rules/"))?;
+        let files = files_view(&files);
+        let rules = Rules::new(&files, &Languages::try_from(&files)?)?;
 
         assert!(!rules.rules.is_empty());
 
@@ -641,10 +856,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rule_type_from_str() {
+        for rule_type in [RuleType::Approx, RuleType::Exact] {
+            assert_eq!(rule_type.to_string().parse::<RuleType>(), Ok(rule_type));
+        }
+        assert_eq!("approx".parse::<RuleType>(), Ok(RuleType::Approx));
+        assert_eq!("exact".parse::<RuleType>(), Ok(RuleType::Exact));
+        assert_eq!(
+            "rules".parse::<RuleType>(),
+            Err(BMError::UnknownRuleType("rules".to_string()))
+        );
+        assert_eq!(
+            "klingon".parse::<RuleType>(),
+            Err(BMError::UnknownRuleType("klingon".to_string()))
+        );
+    }
+
     #[test]
     fn test_parse_rule_include() -> Result<(), PhoneticError> {
+        let files = read_directory(Path::new("./test_assets/test-include/"))?;
+        let files = files_view(&files);
         let resolver = Resolver {
-            path: Some(PathBuf::from("./test_assets/test-include/")),
+            files: Some(&files),
         };
         let tmp = parse_rule(&resolver, "gen_exact_german")?;
         let mut result: BTreeSet<String> = BTreeSet::new();
@@ -660,4 +894,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_rule_error_description() {
+        let resolver = Resolver { files: None };
+        let parse = |content: &str| match parse_rule_content(&resolver, "rules", content) {
+            Err(PhoneticError::ParseRuleError(error)) => error,
+            result => panic!("Expected a parse error, got {result:?}"),
+        };
+
+        let error = parse("\"a\" \"\" \"\" \"a\"\n\"b\" \"\" \"\"\n");
+        assert_eq!(error.line_number, 2);
+        assert_eq!(error.filename, Some("rules".to_string()));
+        assert_eq!(error.line_content, "\"b\" \"\" \"\"");
+        assert_eq!(error.description, "Expected 4 quoted parts, found 3");
+
+        let error = parse("\"a\" \"\" \"\" \"a\n");
+        assert_eq!(error.description, "Unterminated quote");
+
+        let error = parse("This is wrong.");
+        assert_eq!(error.description, "Can't parse line");
+
+        let result = parse_rule_content(&resolver, "rules", "\"a\" \"[a\" \"\" \"a\"");
+        assert!(matches!(
+            result,
+            Err(PhoneticError::BMError(BMError::BadContextRegex(_)))
+        ));
+
+        let result = parse_rule_content(
+            &resolver,
+            "rules",
+            "\"a\" \"\" \"\" \"\"\n\"b\" \"\" \"\" \"(b\"",
+        );
+        assert_eq!(
+            result.err(),
+            Some(PhoneticError::BMError(BMError::WrongPhoneme(
+                "rules:2 Wrong phoneme rule (b".to_string()
+            )))
+        );
+    }
 }
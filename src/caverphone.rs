@@ -14,6 +14,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{helper, Encoder};
@@ -21,22 +25,59 @@ use crate::{helper, Encoder};
 const SIX_1: &str = "111111";
 const TEN_1: &str = "1111111111";
 
-/// This a [Caverphone 1](https://en.wikipedia.org/wiki/Caverphone) encoder.
+/// Revision of the [Caverphone](https://en.wikipedia.org/wiki/Caverphone) algorithm.
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum CaverphoneRevision {
+    /// Revision 1, see [Caverphone1].
+    One,
+    /// Revision 2, see [Caverphone2].
+    #[default]
+    Two,
+}
+
+/// This a [Caverphone](https://en.wikipedia.org/wiki/Caverphone) encoder whose revision
+/// can be selected at runtime. It produces the same codes as [Caverphone1] or
+/// [Caverphone2], depending on its [revision](CaverphoneRevision).
+///
+/// The [Default] implementation uses [revision 2](CaverphoneRevision::Two).
 ///
 /// # Example
 ///
 /// ```rust
-/// use rphonetic::{Caverphone1, Encoder};
-///
-/// let caverphone = Caverphone1;
+/// use rphonetic::{Caverphone, CaverphoneRevision, Encoder};
 ///
+/// let caverphone = Caverphone::new(CaverphoneRevision::One);
 /// assert_eq!(caverphone.encode("Thompson"), "TMPSN1");
+///
+/// let caverphone = Caverphone::new(CaverphoneRevision::Two);
+/// assert_eq!(caverphone.encode("Thompson"), "TMPSN11111");
 /// ```
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub struct Caverphone1;
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub struct Caverphone {
+    revision: CaverphoneRevision,
+}
 
-impl Encoder for Caverphone1 {
-    fn encode(&self, s: &str) -> String {
+impl Caverphone {
+    /// Construct a new [Caverphone] with the revision provided.
+    ///
+    /// # Parameter
+    ///
+    /// * `revision`: the revision of the algorithm.
+    pub fn new(revision: CaverphoneRevision) -> Self {
+        Self { revision }
+    }
+
+    /// Return the revision of the algorithm.
+    pub fn revision(&self) -> CaverphoneRevision {
+        self.revision
+    }
+
+    /// Encode `s` with [revision 1](CaverphoneRevision::One).
+    fn encode_revision_one(s: &str) -> String {
         if s.is_empty() {
             return SIX_1.to_string();
         }
@@ -137,33 +178,31 @@ impl Encoder for Caverphone1 {
 
         txt[0..SIX_1.len()].to_string()
     }
-}
 
-/// This a [Caverphone 2](https://en.wikipedia.org/wiki/Caverphone) encoder.
-///
-/// # Example
-///
-/// ```rust
-/// use rphonetic::{Caverphone2, Encoder};
-///
-/// let caverphone = Caverphone2;
-///
-/// assert_eq!(caverphone.encode("Thompson"), "TMPSN11111");
-/// ```
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub struct Caverphone2;
-
-impl Encoder for Caverphone2 {
-    fn encode(&self, s: &str) -> String {
-        if s.is_empty() {
-            return TEN_1.to_string();
-        }
+    /// Return the value after each stage of [revision 2](CaverphoneRevision::Two),
+    /// see [trace](Caverphone2::trace).
+    fn trace_revision_two(s: &str) -> Vec<(&'static str, String)> {
+        let mut stages = Vec::new();
 
         let txt = s.to_lowercase();
+        stages.push(("lowercase", txt.clone()));
 
         let txt = helper::remove_all_non_letter(txt);
+        stages.push(("remove non letters", txt.clone()));
 
+        let mut code = String::with_capacity(TEN_1.len());
+        Self::encode_letters(txt, &mut code, |stage, txt| {
+            stages.push((stage, txt.to_string()))
+        });
+
+        stages
+    }
+
+    /// Encode `txt`, only made of lowercase letters, and append the code to `out`.
+    /// `trace` is called after each stage with the stage's name and the current value.
+    fn encode_letters(txt: String, out: &mut String, mut trace: impl FnMut(&'static str, &str)) {
         let txt = helper::replace_end(txt, "e", "");
+        trace("remove final e", &txt);
 
         let txt = if txt.starts_with("cough") {
             txt.replacen("cough", "cou2f", 1)
@@ -196,7 +235,10 @@ impl Encoder for Caverphone2 {
             txt
         };
 
+        trace("initial ough and gn", &txt);
+
         let txt = helper::replace_end(txt, "mb", "m2");
+        trace("final mb", &txt);
 
         let txt = txt.replace("cq", "2q");
         let txt = txt.replace("ci", "si");
@@ -215,6 +257,8 @@ impl Encoder for Caverphone2 {
         let txt = txt.replace('b', "p");
         let txt = txt.replace("sh", "s2");
         let txt = txt.replace('z', "s");
+        trace("consonants", &txt);
+
         let txt = helper::replace_char(txt, |(i, c)| {
             if i == 0 && helper::is_vowel(Some(c), false) {
                 'A'
@@ -230,6 +274,8 @@ impl Encoder for Caverphone2 {
                 c
             }
         });
+        trace("vowels", &txt);
+
         let txt = txt.replace('j', "y");
         let txt = if txt.starts_with("y3") {
             txt.replacen("y3", "Y3", 1)
@@ -242,35 +288,200 @@ impl Encoder for Caverphone2 {
             txt
         };
         let txt = txt.replace('y', "3");
+        trace("j and y", &txt);
+
         let txt = txt.replace("3gh3", "3kh3");
         let txt = txt.replace("gh", "22");
         let txt = txt.replace('g', "k");
+        trace("gh and g", &txt);
+
         let txt =
             helper::replace_compact_all_to_uppercase(txt, vec!['s', 't', 'p', 'k', 'f', 'm', 'n']);
+        trace("compact consonants", &txt);
+
         let txt = txt.replace("w3", "W3");
         let txt = txt.replace("wh3", "Wh3");
         let txt = helper::replace_end(txt, "w", "3");
         let txt = txt.replace('w', "2");
+        trace("w", &txt);
+
         let txt = if txt.starts_with('h') {
             txt.replacen('h', "A", 1)
         } else {
             txt
         };
         let txt = txt.replace('h', "2");
+        trace("h", &txt);
+
         let txt = txt.replace("r3", "R3");
         let txt = helper::replace_end(txt, "r", "3");
         let txt = txt.replace('r', "2");
         let txt = txt.replace("l3", "L3");
         let txt = helper::replace_end(txt, "l", "3");
         let txt = txt.replace('l', "2");
+        trace("r and l", &txt);
 
         let txt = txt.replace('2', "");
         let txt = helper::replace_end(txt, "3", "A");
         let txt = txt.replace('3', "");
+        trace("remove 2 and 3", &txt);
 
         let txt = txt + TEN_1;
 
-        txt[0..TEN_1.len()].to_string()
+        out.push_str(&txt[0..TEN_1.len()]);
+        trace("pad", out);
+    }
+}
+
+impl From<Caverphone1> for Caverphone {
+    fn from(_: Caverphone1) -> Self {
+        Self::new(CaverphoneRevision::One)
+    }
+}
+
+impl From<Caverphone2> for Caverphone {
+    fn from(_: Caverphone2) -> Self {
+        Self::new(CaverphoneRevision::Two)
+    }
+}
+
+impl Encoder for Caverphone {
+    fn encode(&self, s: &str) -> String {
+        match self.revision {
+            CaverphoneRevision::One => Self::encode_revision_one(s),
+            CaverphoneRevision::Two => {
+                let mut code = String::with_capacity(TEN_1.len());
+                self.encode_into(s, &mut code);
+                code
+            }
+        }
+    }
+
+    fn encode_into(&self, s: &str, out: &mut String) {
+        out.clear();
+        match self.revision {
+            CaverphoneRevision::One => out.push_str(&Self::encode_revision_one(s)),
+            CaverphoneRevision::Two => {
+                if s.is_empty() {
+                    out.push_str(TEN_1);
+                    return;
+                }
+
+                let txt = s.to_lowercase();
+
+                let txt = helper::remove_all_non_letter(txt);
+
+                Self::encode_letters(txt, out, |_, _| {});
+            }
+        }
+    }
+
+    /// Encode `value` without UTF-8 validation. With revision 2, non-ASCII bytes are skipped.
+    fn encode_bytes(&self, s: &[u8]) -> Vec<u8> {
+        match self.revision {
+            CaverphoneRevision::One => {
+                Self::encode_revision_one(&String::from_utf8_lossy(s)).into_bytes()
+            }
+            CaverphoneRevision::Two => {
+                if s.is_empty() {
+                    return TEN_1.as_bytes().to_vec();
+                }
+
+                let txt: String = s
+                    .iter()
+                    .filter(|b| b.is_ascii_alphabetic())
+                    .map(|b| char::from(b.to_ascii_lowercase()))
+                    .collect();
+
+                let mut code = String::with_capacity(TEN_1.len());
+                Self::encode_letters(txt, &mut code, |_, _| {});
+                code.into_bytes()
+            }
+        }
+    }
+}
+
+/// This a [Caverphone 1](https://en.wikipedia.org/wiki/Caverphone) encoder.
+///
+/// It is the same as [Caverphone] with [revision 1](CaverphoneRevision::One).
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Caverphone1, Encoder};
+///
+/// let caverphone = Caverphone1;
+///
+/// assert_eq!(caverphone.encode("Thompson"), "TMPSN1");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Caverphone1;
+
+impl Encoder for Caverphone1 {
+    fn encode(&self, s: &str) -> String {
+        Caverphone::from(*self).encode(s)
+    }
+}
+
+/// This a [Caverphone 2](https://en.wikipedia.org/wiki/Caverphone) encoder.
+///
+/// It is the same as [Caverphone] with [revision 2](CaverphoneRevision::Two).
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Caverphone2, Encoder};
+///
+/// let caverphone = Caverphone2;
+///
+/// assert_eq!(caverphone.encode("Thompson"), "TMPSN11111");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Caverphone2;
+
+impl Encoder for Caverphone2 {
+    fn encode(&self, s: &str) -> String {
+        Caverphone::from(*self).encode(s)
+    }
+
+    fn encode_into(&self, s: &str, out: &mut String) {
+        Caverphone::from(*self).encode_into(s, out)
+    }
+
+    /// Encode `value` without UTF-8 validation, non-ASCII bytes are skipped.
+    fn encode_bytes(&self, s: &[u8]) -> Vec<u8> {
+        Caverphone::from(*self).encode_bytes(s)
+    }
+}
+
+impl Caverphone2 {
+    /// Return the value after each stage of the algorithm, along with the stage's name.
+    /// This is meant to understand why a value is encoded as it is, the last stage
+    /// is the code returned by [encode](Encoder::encode).
+    ///
+    /// Stage names are informative and may change.
+    ///
+    /// # Parameter
+    ///
+    /// * `s`: the value to trace.
+    ///
+    /// # Return
+    ///
+    /// The name of each stage, in order, with the value after the stage.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Caverphone2, Encoder};
+    ///
+    /// let caverphone = Caverphone2;
+    /// let trace = caverphone.trace("Thompson");
+    ///
+    /// assert_eq!(trace[0], ("lowercase", "thompson".to_string()));
+    /// assert_eq!(trace.last(), Some(&("pad", caverphone.encode("Thompson"))));
+    /// ```
+    pub fn trace(&self, s: &str) -> Vec<(&'static str, String)> {
+        Caverphone::trace_revision_two(s)
     }
 }
 
@@ -478,4 +689,93 @@ mod tests {
         assert_eq!(caverphone.encode("Karleen"), "KLN1111111");
         assert_eq!(caverphone.encode("Dyun"), "TN11111111");
     }
+
+    #[test]
+    fn test_revision() {
+        let words = [
+            "",
+            "Thompson",
+            "Peter",
+            "Stevenson",
+            "mbmb",
+            "Karleen",
+            "tough",
+            "Lleyton-Hewitt",
+        ];
+
+        let caverphone = Caverphone::new(CaverphoneRevision::One);
+        assert_eq!(caverphone, Caverphone::from(Caverphone1));
+        assert_eq!(caverphone.revision(), CaverphoneRevision::One);
+        for word in words {
+            assert_eq!(
+                caverphone.encode(word).as_bytes(),
+                Caverphone1.encode(word).as_bytes()
+            );
+        }
+
+        let caverphone = Caverphone::new(CaverphoneRevision::Two);
+        assert_eq!(caverphone, Caverphone::default());
+        assert_eq!(caverphone, Caverphone::from(Caverphone2));
+        for word in words {
+            assert_eq!(
+                caverphone.encode(word).as_bytes(),
+                Caverphone2.encode(word).as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        for value in [
+            "Thompson",
+            "",
+            "Tough",
+            "Peter",
+            "Stevenson",
+            "O'Brien",
+            "2-Mb",
+        ] {
+            assert_eq!(
+                Caverphone2.encode_bytes(value.as_bytes()),
+                Caverphone2.encode(value).as_bytes()
+            );
+            assert_eq!(
+                Caverphone::new(CaverphoneRevision::One).encode_bytes(value.as_bytes()),
+                Caverphone1.encode(value).as_bytes()
+            );
+        }
+
+        assert_eq!(
+            Caverphone2.encode_bytes(b"Tho\xFFmpson"),
+            Caverphone2.encode("Thompson").as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let caverphone = Caverphone2;
+
+        for value in ["Thompson", "Stevenson", "Mississippi", "Tough", " ", ""] {
+            let trace = caverphone.trace(value);
+            assert!(!trace.is_empty());
+            assert_eq!(trace.last().unwrap().1, caverphone.encode(value));
+        }
+
+        let trace = caverphone.trace("Thompson");
+        assert_eq!(trace.first(), Some(&("lowercase", "thompson".to_string())));
+        assert!(trace.contains(&("h", "T23MPS3N".to_string())));
+        assert!(trace.contains(&("remove 2 and 3", "TMPSN".to_string())));
+        assert_eq!(trace.last(), Some(&("pad", "TMPSN11111".to_string())));
+    }
+
+    #[test]
+    fn test_encode_into() {
+        let mut buffer = String::from("previous content");
+        for value in ["Thompson", "", "Tough", "Peter"] {
+            Caverphone2.encode_into(value, &mut buffer);
+            assert_eq!(buffer, Caverphone2.encode(value));
+            Caverphone::new(CaverphoneRevision::One).encode_into(value, &mut buffer);
+            assert_eq!(buffer, Caverphone1.encode(value));
+        }
+    }
 }
@@ -14,9 +14,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
 use serde::{Deserialize, Serialize};
 
-use crate::Encoder;
+use crate::{Encoder, SoundexCommons};
 
 const CHAR_IGNORE: char = '-';
 const AEIJOUY: [char; 7] = ['A', 'E', 'I', 'J', 'O', 'U', 'Y'];
@@ -28,6 +31,8 @@ const AHKLOQRUX: [char; 9] = ['A', 'H', 'K', 'L', 'O', 'Q', 'R', 'U', 'X'];
 const SZ: [char; 2] = ['S', 'Z'];
 const AHKOQUX: [char; 7] = ['A', 'H', 'K', 'O', 'Q', 'U', 'X'];
 const DTX: [char; 3] = ['D', 'T', 'X'];
+/// Number of digits of a code that always fit in a [u64].
+const U64_DIGITS: usize = 19;
 
 struct CologneOutput {
     last_char: char,
@@ -51,8 +56,29 @@ impl CologneOutput {
     }
 }
 
+/// Uppercase `value` and transcribe German specific letters: umlauts lose their
+/// diaeresis and `ß` (both lowercase and uppercase) becomes `SS`.
+fn preprocess(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for ch in value.chars().flat_map(char::to_uppercase) {
+        match ch {
+            'Ä' => result.push('A'),
+            'Ö' => result.push('O'),
+            'Ü' => result.push('U'),
+            // Lowercase 'ß' is already uppercased to "SS"
+            'ẞ' => result.push_str("SS"),
+            _ => result.push(ch),
+        }
+    }
+
+    result
+}
+
 /// This a [Cologne](https://en.wikipedia.org/wiki/Cologne_phonetics) encoder.
 ///
+/// German umlauts and `ß` are transcribed before encoding, so `Schäfer` and
+/// `Schaefer` have the same code.
+///
 /// # Example :
 ///
 /// ```rust
@@ -65,15 +91,90 @@ impl CologneOutput {
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Cologne;
 
+impl Cologne {
+    /// Encode `value` and pack its code into a [u64] for numeric indexing. The code
+    /// is left-aligned on 19 digits and padded with zeros, eg. `657` becomes
+    /// `6_570_000_000_000_000_000`.
+    ///
+    /// As only the first digit of a code can be `0`, two different codes always result
+    /// in two different numbers.
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to encode.
+    ///
+    /// # Return
+    ///
+    /// The packed code, or [None] if the code is empty or has more than 19 digits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Cologne;
+    ///
+    /// let cologne = Cologne;
+    ///
+    /// assert_eq!(cologne.encode_u64("M\u{00FC}ller"), Some(6_570_000_000_000_000_000));
+    /// assert_eq!(cologne.encode_u64(""), None);
+    /// ```
+    pub fn encode_u64(&self, value: &str) -> Option<u64> {
+        let code = self.encode(value);
+        if code.is_empty() || code.len() > U64_DIGITS {
+            return None;
+        }
+
+        code.bytes()
+            .chain(core::iter::repeat(b'0'))
+            .take(U64_DIGITS)
+            .try_fold(0u64, |result, digit| {
+                char::from(digit)
+                    .to_digit(10)
+                    .map(|digit| result * 10 + digit as u64)
+            })
+    }
+
+    /// Encode each whitespace separated word of `text` and count how many
+    /// times each code occurs. Words are folded (uppercase and umlaut
+    /// transcription) as in [encode](Encoder::encode). Words that produce
+    /// an empty code are ignored.
+    ///
+    /// # Parameter
+    ///
+    /// * `text`: text to encode.
+    ///
+    /// # Return
+    ///
+    /// A map of codes and their number of occurrences.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Cologne;
+    ///
+    /// let cologne = Cologne;
+    ///
+    /// let counts = cologne.encode_text_counts("Meyer Maier M\u{00FC}ller");
+    ///
+    /// assert_eq!(counts.get("67"), Some(&2));
+    /// assert_eq!(counts.get("657"), Some(&1));
+    /// ```
+    pub fn encode_text_counts(&self, text: &str) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+
+        text.split_whitespace()
+            .map(|word| self.encode(word))
+            .filter(|code| !code.is_empty())
+            .for_each(|code| *counts.entry(code).or_insert(0) += 1);
+
+        counts
+    }
+}
+
 impl Encoder for Cologne {
     fn encode(&self, s: &str) -> String {
         let mut output = CologneOutput::with_capacity(s.len());
 
-        // Uppercase and aumlaut transcription
-        let mut tmp = s.to_uppercase();
-        tmp = tmp.replace('Ä', "A");
-        tmp = tmp.replace('Ü', "U");
-        tmp = tmp.replace('Ö', "O");
+        let tmp = preprocess(s);
 
         let mut last_char = CHAR_IGNORE;
 
@@ -132,10 +233,28 @@ impl Encoder for Cologne {
     }
 }
 
+/// Cologne codes are digits, so they can be compared position by position
+/// with [difference](SoundexCommons::difference). Unlike [Soundex](crate::Soundex),
+/// codes have a variable length: the result is at most the length of the shortest
+/// code, so it should not be compared between pairs of different lengths.
+/// [code_edit_distance](Encoder::code_edit_distance) may be better suited.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Cologne, SoundexCommons};
+///
+/// let cologne = Cologne;
+///
+/// assert_eq!(cologne.difference("M\u{00FC}ller", "Mueller"), 3);
+/// assert_eq!(cologne.difference("M\u{00FC}ller", "Schmidt"), 0);
+/// ```
+impl SoundexCommons for Cologne {}
+
 #[cfg(test)]
 mod tests {
-    use crate::cologne::Cologne;
-    use crate::Encoder;
+    use crate::cologne::{preprocess, Cologne};
+    use crate::{Encoder, SoundexCommons};
 
     #[test]
     fn test_aabjoe() {
@@ -258,6 +377,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_text_counts() {
+        let cologne = Cologne;
+
+        let counts = cologne.encode_text_counts("Meyer Maier  trifft\tM\u{00DC}LLER und Mueller !");
+
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts.get("67"), Some(&2));
+        assert_eq!(counts.get("657"), Some(&2));
+        assert_eq!(counts.get("2732"), Some(&1));
+        assert_eq!(counts.get("062"), Some(&1));
+
+        assert!(cologne.encode_text_counts("  ").is_empty());
+    }
+
+    #[test]
+    fn test_encode_bounded() {
+        let cologne = Cologne;
+
+        let value = "Leutheusser-Schnarrenberger";
+        assert_eq!(cologne.encode(value), "528786761747");
+        assert_eq!(cologne.encode_bounded(value, 6), "528786");
+        assert_eq!(cologne.encode_bounded(value, 100), "528786761747");
+        assert_eq!(cologne.encode_bounded(value, 0), "");
+        assert!(value
+            .split('-')
+            .all(|v| cologne.encode_bounded(v, 6).chars().count() <= 6));
+    }
+
+    #[test]
+    fn test_encode_u64() {
+        let cologne = Cologne;
+
+        assert_eq!(cologne.encode_u64("Meyer"), Some(6_700_000_000_000_000_000));
+        assert_eq!(cologne.encode_u64("Aachen"), Some(460_000_000_000_000_000));
+        assert_eq!(cologne.encode_u64("A"), Some(0));
+        assert_eq!(
+            cologne.encode_u64("Leutheusser-Schnarrenberger"),
+            Some(5_287_867_617_470_000_000)
+        );
+        assert_eq!(cologne.encode_u64(""), None);
+        assert_eq!(cologne.encode_u64("!!"), None);
+
+        // Longest code that fits
+        let value = "bdbdbdbdbdbdbdbdbdb";
+        assert_eq!(cologne.encode(value).len(), 19);
+        assert_eq!(cologne.encode_u64(value), Some(1_212_121_212_121_212_121));
+
+        // Too long
+        let value = "bdbdbdbdbdbdbdbdbdbd";
+        assert_eq!(cologne.encode(value).len(), 20);
+        assert_eq!(cologne.encode_u64(value), None);
+    }
+
     #[test]
     fn test_is_encode_equals() {
         let data: Vec<(&str, &str)> = vec![
@@ -277,6 +450,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_preprocess() {
+        assert_eq!(preprocess("Sch\u{00E4}fer"), "SCHAFER");
+        assert_eq!(preprocess("\u{00D6}l \u{00FC}ber"), "OL UBER");
+        assert_eq!(preprocess("Stra\u{00DF}e"), "STRASSE");
+        assert_eq!(preprocess("STRA\u{1E9E}E"), "STRASSE");
+    }
+
+    #[test]
+    fn test_umlauts_and_sharp_s() {
+        assert_eq!(Cologne.encode("Sch\u{00E4}fer"), Cologne.encode("Schaefer"));
+        assert_eq!(Cologne.encode("SCH\u{00C4}FER"), Cologne.encode("Schaefer"));
+        assert_eq!(
+            Cologne.encode("Gr\u{00F6}\u{00DF}e"),
+            Cologne.encode("Groesse")
+        );
+        assert_eq!(Cologne.encode("Stra\u{00DF}e"), "8278");
+        assert_eq!(Cologne.encode("STRA\u{1E9E}E"), "8278");
+    }
+
+    #[test]
+    fn test_difference() {
+        // Same code
+        assert_eq!(Cologne.difference("M\u{00FC}ller", "Mueller"), 3);
+        assert_eq!(Cologne.difference("Schmidt", "Schmitt"), 3);
+        // Common prefix, different lengths
+        assert_eq!(Cologne.difference("M\u{00FC}ller", "M\u{00FC}llermann"), 3);
+        // Dissimilar
+        assert_eq!(Cologne.difference("M\u{00FC}ller", "Schmidt"), 0);
+        // Empty code
+        assert_eq!(Cologne.difference("M\u{00FC}ller", ""), 0);
+    }
+
     #[test]
     fn test_variations_mella() {
         let data: Vec<&str> = vec!["mella", "milah", "moulla", "mellah", "muehle", "mule"];
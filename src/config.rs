@@ -0,0 +1,684 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use enum_iterator::Sequence;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Caverphone, Caverphone1, Caverphone2, CaverphoneRevision, Cologne, CustomMatchRatingApproach,
+    DoubleMetaphone, Encoder, HwRule, InitialVowel, MatchRatingApproach, Metaphone,
+    NonLetterPolicy, Nysiis, NysiisBuilder, PhoneticError, Phonex, Phonix, RefinedSoundex, Soundex,
+};
+
+fn default_code_length() -> Option<usize> {
+    Some(4)
+}
+
+fn default_usize_code_length() -> usize {
+    4
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_codex_keep() -> usize {
+    3
+}
+
+fn check_length(name: &str, length: usize) -> Result<(), PhoneticError> {
+    if length == 0 {
+        return Err(PhoneticError::InvalidParameter {
+            name: name.to_string(),
+            reason: "must be greater than 0".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// This is the configuration of an encoder, to select and configure the algorithm
+/// from a configuration file with any `serde` format.
+///
+/// The algorithm is given by the `algorithm` field, other fields are its settings.
+/// Every setting is optional and defaults to the [Default] implementation of the encoder.
+/// Encoders that need rules files ([BeiderMorse](crate::BeiderMorse) and
+/// [DaitchMokotoffSoundex](crate::DaitchMokotoffSoundex)) are not supported.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> Result<(), rphonetic::PhoneticError> {
+/// use rphonetic::{Encoder, EncoderConfig};
+///
+/// let config = EncoderConfig::Soundex {
+///     mapping: None,
+///     max_length: 6,
+///     padding: true,
+///     lowercase_output: false,
+///     non_letter_policy: Default::default(),
+///     normalize_initial: false,
+///     legacy_first_group: false,
+///     encode_first_letter: false,
+///     hw_rule: Default::default(),
+/// };
+/// let encoder = config.build()?;
+///
+/// assert_eq!(encoder.encode("Washington"), "W25235");
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "snake_case")]
+pub enum EncoderConfig {
+    /// [Caverphone] configuration.
+    Caverphone {
+        /// Revision of the algorithm, default to 2.
+        #[serde(default)]
+        revision: CaverphoneRevision,
+    },
+    /// [Cologne] configuration.
+    Cologne,
+    /// [DoubleMetaphone] configuration.
+    DoubleMetaphone {
+        /// Maximum code length, default to 4. `null` for no maximum.
+        #[serde(default = "default_code_length")]
+        max_code_length: Option<usize>,
+        /// See [respect_internal_spaces](DoubleMetaphone::respect_internal_spaces).
+        #[serde(default)]
+        respect_internal_spaces: bool,
+        /// See [with_silent_letters](DoubleMetaphone::with_silent_letters).
+        #[serde(default)]
+        silent_letters: BTreeSet<char>,
+        /// See [initial_vowel](DoubleMetaphone::initial_vowel).
+        #[serde(default)]
+        initial_vowel: InitialVowel,
+    },
+    /// [MatchRatingApproach] configuration.
+    MatchRatingApproach {
+        /// See [with_codex_keep](MatchRatingApproach::with_codex_keep), default to 3.
+        #[serde(default = "default_codex_keep")]
+        codex_keep: usize,
+    },
+    /// [Metaphone] configuration.
+    Metaphone {
+        /// Maximum code length, default to 4. `null` for no maximum.
+        #[serde(default = "default_code_length")]
+        max_code_length: Option<usize>,
+    },
+    /// [Nysiis] configuration.
+    Nysiis {
+        /// Strict mode, default to `true`.
+        #[serde(default = "default_true")]
+        strict: bool,
+        /// See [preserve_initial_vowel](NysiisBuilder::preserve_initial_vowel), default to `true`.
+        #[serde(default = "default_true")]
+        preserve_initial_vowel: bool,
+        /// See [max_length](NysiisBuilder::max_length), it replaces the length given
+        /// by `strict` if set.
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+    /// [Phonex] configuration.
+    Phonex {
+        /// Maximum code length, default to 4.
+        #[serde(default = "default_usize_code_length")]
+        max_code_length: usize,
+    },
+    /// [Phonix] configuration.
+    Phonix {
+        /// Maximum code length, default to 4.
+        #[serde(default = "default_usize_code_length")]
+        max_code_length: usize,
+    },
+    /// [RefinedSoundex] configuration.
+    RefinedSoundex {
+        /// Mapping, as for [from_mapping](RefinedSoundex::from_mapping), default to US english mapping.
+        #[serde(default)]
+        mapping: Option<String>,
+        /// See [lowercase_output](RefinedSoundex::lowercase_output).
+        #[serde(default)]
+        lowercase_output: bool,
+        /// See [non_letter_policy](RefinedSoundex::non_letter_policy).
+        #[serde(default)]
+        non_letter_policy: NonLetterPolicy,
+    },
+    /// [Soundex] configuration.
+    Soundex {
+        /// Mapping, as for [from_mapping](Soundex::from_mapping), default to US english mapping.
+        #[serde(default)]
+        mapping: Option<String>,
+        /// See [max_length](Soundex::max_length), default to 4.
+        #[serde(default = "default_usize_code_length")]
+        max_length: usize,
+        /// See [padding](Soundex::padding), default to `true`.
+        #[serde(default = "default_true")]
+        padding: bool,
+        /// See [lowercase_output](Soundex::lowercase_output).
+        #[serde(default)]
+        lowercase_output: bool,
+        /// See [non_letter_policy](Soundex::non_letter_policy).
+        #[serde(default)]
+        non_letter_policy: NonLetterPolicy,
+        /// See [normalize_initial](Soundex::normalize_initial).
+        #[serde(default)]
+        normalize_initial: bool,
+        /// See [legacy_first_group](Soundex::legacy_first_group).
+        #[serde(default)]
+        legacy_first_group: bool,
+        /// See [encode_first_letter](Soundex::encode_first_letter).
+        #[serde(default)]
+        encode_first_letter: bool,
+        /// See [hw_rule](Soundex::hw_rule), ignored if not set.
+        #[serde(default)]
+        hw_rule: Option<HwRule>,
+    },
+}
+
+impl EncoderConfig {
+    /// Build the encoder described by this configuration.
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::InvalidMapping] if a mapping is not valid or a
+    /// [PhoneticError::InvalidParameter] if a length is 0.
+    pub fn build(self) -> Result<AnyEncoder, PhoneticError> {
+        let encoder = match self {
+            Self::Caverphone { revision } => AnyEncoder::Caverphone(Caverphone::new(revision)),
+            Self::Cologne => AnyEncoder::Cologne(Cologne),
+            Self::DoubleMetaphone {
+                max_code_length,
+                respect_internal_spaces,
+                silent_letters,
+                initial_vowel,
+            } => {
+                if let Some(max_code_length) = max_code_length {
+                    check_length("max_code_length", max_code_length)?;
+                }
+                AnyEncoder::DoubleMetaphone(
+                    DoubleMetaphone::new(max_code_length)
+                        .respect_internal_spaces(respect_internal_spaces)
+                        .with_silent_letters(&silent_letters)
+                        .initial_vowel(initial_vowel),
+                )
+            }
+            Self::MatchRatingApproach { codex_keep } => {
+                check_length("codex_keep", codex_keep)?;
+                AnyEncoder::MatchRatingApproach(MatchRatingApproach.with_codex_keep(codex_keep))
+            }
+            Self::Metaphone { max_code_length } => {
+                if let Some(max_code_length) = max_code_length {
+                    check_length("max_code_length", max_code_length)?;
+                }
+                AnyEncoder::Metaphone(Metaphone::new(max_code_length))
+            }
+            Self::Nysiis {
+                strict,
+                preserve_initial_vowel,
+                max_length,
+            } => {
+                let mut builder = NysiisBuilder::default()
+                    .strict(strict)
+                    .preserve_initial_vowel(preserve_initial_vowel);
+                if let Some(max_length) = max_length {
+                    check_length("max_length", max_length)?;
+                    builder = builder.max_length(Some(max_length));
+                }
+                AnyEncoder::Nysiis(builder.build())
+            }
+            Self::Phonex { max_code_length } => {
+                check_length("max_code_length", max_code_length)?;
+                AnyEncoder::Phonex(Phonex::new(max_code_length))
+            }
+            Self::Phonix { max_code_length } => {
+                check_length("max_code_length", max_code_length)?;
+                AnyEncoder::Phonix(Phonix::new(max_code_length))
+            }
+            Self::RefinedSoundex {
+                mapping,
+                lowercase_output,
+                non_letter_policy,
+            } => {
+                let refined_soundex = match mapping {
+                    None => RefinedSoundex::default(),
+                    Some(mapping) => RefinedSoundex::from_mapping(&mapping)?,
+                };
+                AnyEncoder::RefinedSoundex(
+                    refined_soundex
+                        .lowercase_output(lowercase_output)
+                        .non_letter_policy(non_letter_policy),
+                )
+            }
+            Self::Soundex {
+                mapping,
+                max_length,
+                padding,
+                lowercase_output,
+                non_letter_policy,
+                normalize_initial,
+                legacy_first_group,
+                encode_first_letter,
+                hw_rule,
+            } => {
+                check_length("max_length", max_length)?;
+                let soundex = match mapping {
+                    None => Soundex::default(),
+                    Some(mapping) => Soundex::from_mapping(&mapping)?,
+                };
+                let soundex = match hw_rule {
+                    None => soundex,
+                    Some(hw_rule) => soundex.hw_rule(hw_rule),
+                };
+                AnyEncoder::Soundex(
+                    soundex
+                        .max_length(max_length)
+                        .padding(padding)
+                        .lowercase_output(lowercase_output)
+                        .non_letter_policy(non_letter_policy)
+                        .normalize_initial(normalize_initial)
+                        .legacy_first_group(legacy_first_group)
+                        .encode_first_letter(encode_first_letter),
+                )
+            }
+        };
+
+        Ok(encoder)
+    }
+}
+
+/// This is an encoder whose algorithm is selected at runtime, usually built
+/// from an [EncoderConfig]. It implements [Encoder] by delegating to the
+/// selected encoder.
+// Encoders are kept inline, to avoid an indirection on each encoding
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnyEncoder {
+    /// A [Caverphone] encoder.
+    Caverphone(Caverphone),
+    /// A [Cologne] encoder.
+    Cologne(Cologne),
+    /// A [DoubleMetaphone] encoder.
+    DoubleMetaphone(DoubleMetaphone),
+    /// A [MatchRatingApproach] encoder.
+    MatchRatingApproach(CustomMatchRatingApproach),
+    /// A [Metaphone] encoder.
+    Metaphone(Metaphone),
+    /// A [Nysiis] encoder.
+    Nysiis(Nysiis),
+    /// A [Phonex] encoder.
+    Phonex(Phonex),
+    /// A [Phonix] encoder.
+    Phonix(Phonix),
+    /// A [RefinedSoundex] encoder.
+    RefinedSoundex(RefinedSoundex),
+    /// A [Soundex] encoder.
+    Soundex(Soundex),
+}
+
+impl AnyEncoder {
+    fn inner(&self) -> &dyn Encoder {
+        match self {
+            Self::Caverphone(encoder) => encoder,
+            Self::Cologne(encoder) => encoder,
+            Self::DoubleMetaphone(encoder) => encoder,
+            Self::MatchRatingApproach(encoder) => encoder,
+            Self::Metaphone(encoder) => encoder,
+            Self::Nysiis(encoder) => encoder,
+            Self::Phonex(encoder) => encoder,
+            Self::Phonix(encoder) => encoder,
+            Self::RefinedSoundex(encoder) => encoder,
+            Self::Soundex(encoder) => encoder,
+        }
+    }
+}
+
+impl Encoder for AnyEncoder {
+    fn encode(&self, s: &str) -> String {
+        self.inner().encode(s)
+    }
+
+    fn encode_into(&self, s: &str, out: &mut String) {
+        self.inner().encode_into(s, out)
+    }
+
+    fn is_encoded_equals(&self, first: &str, second: &str) -> bool {
+        self.inner().is_encoded_equals(first, second)
+    }
+}
+
+/// This is the list of algorithms that don't need rules, to select one by its name,
+/// for instance from a command line argument. [encoder](Algorithm::encoder) returns
+/// the [Default] encoder of the algorithm; use [EncoderConfig] to configure it.
+///
+/// Names are in snake case, eg. `double_metaphone` (see [FromStr] and [Display]).
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> Result<(), rphonetic::PhoneticError> {
+/// use rphonetic::Algorithm;
+///
+/// let algorithm: Algorithm = "double_metaphone".parse()?;
+/// let encoder = algorithm.encoder();
+///
+/// assert_eq!(encoder.encode("Robert"), "RPRT");
+/// assert_eq!(algorithm.to_string(), "double_metaphone");
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(
+    Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Sequence,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    /// [Caverphone1] algorithm.
+    Caverphone1,
+    /// [Caverphone2] algorithm.
+    Caverphone2,
+    /// [Cologne] algorithm.
+    Cologne,
+    /// [DoubleMetaphone] algorithm.
+    DoubleMetaphone,
+    /// [MatchRatingApproach] algorithm.
+    MatchRatingApproach,
+    /// [Metaphone] algorithm.
+    Metaphone,
+    /// [Nysiis] algorithm.
+    Nysiis,
+    /// [Phonex] algorithm.
+    Phonex,
+    /// [Phonix] algorithm.
+    Phonix,
+    /// [RefinedSoundex] algorithm.
+    RefinedSoundex,
+    /// [Soundex] algorithm.
+    Soundex,
+}
+
+impl Algorithm {
+    /// Name of the algorithm, as parsed by [FromStr].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Caverphone1 => "caverphone1",
+            Self::Caverphone2 => "caverphone2",
+            Self::Cologne => "cologne",
+            Self::DoubleMetaphone => "double_metaphone",
+            Self::MatchRatingApproach => "match_rating_approach",
+            Self::Metaphone => "metaphone",
+            Self::Nysiis => "nysiis",
+            Self::Phonex => "phonex",
+            Self::Phonix => "phonix",
+            Self::RefinedSoundex => "refined_soundex",
+            Self::Soundex => "soundex",
+        }
+    }
+
+    /// Build the [Default] encoder of this algorithm.
+    pub fn encoder(&self) -> Box<dyn Encoder> {
+        match self {
+            Self::Caverphone1 => Box::new(Caverphone1),
+            Self::Caverphone2 => Box::new(Caverphone2),
+            Self::Cologne => Box::new(Cologne),
+            Self::DoubleMetaphone => Box::new(DoubleMetaphone::default()),
+            Self::MatchRatingApproach => Box::new(MatchRatingApproach),
+            Self::Metaphone => Box::new(Metaphone::default()),
+            Self::Nysiis => Box::new(Nysiis::default()),
+            Self::Phonex => Box::new(Phonex::default()),
+            Self::Phonix => Box::new(Phonix::default()),
+            Self::RefinedSoundex => Box::new(RefinedSoundex::default()),
+            Self::Soundex => Box::new(Soundex::default()),
+        }
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = PhoneticError;
+
+    /// Parse the name of an algorithm.
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::UnknownAlgorithm] if the name is unknown.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        enum_iterator::all::<Self>()
+            .find(|algorithm| algorithm.name() == value)
+            .ok_or_else(|| PhoneticError::UnknownAlgorithm(value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_soundex() -> Result<(), PhoneticError> {
+        let config: EncoderConfig =
+            serde_json::from_str(r#"{"algorithm": "soundex", "max_length": 6}"#).unwrap();
+        let encoder = config.build()?;
+
+        assert_eq!(
+            encoder,
+            AnyEncoder::Soundex(Soundex::default().max_length(6))
+        );
+        assert_eq!(encoder.encode("Washington"), "W25235");
+        assert_eq!(encoder.encode("Lee"), "L00000");
+
+        let config: EncoderConfig = serde_json::from_str(
+            r#"{"algorithm": "soundex", "mapping": "01230120022455012623010202", "padding": false}"#,
+        )
+        .unwrap();
+        assert_eq!(config.build()?.encode("Lee"), "L");
+
+        let config: EncoderConfig =
+            serde_json::from_str(r#"{"algorithm": "soundex", "hw_rule": "Separator"}"#).unwrap();
+        assert_eq!(config.build()?.encode("Ashcraft"), "A226");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_defaults() -> Result<(), PhoneticError> {
+        let data: Vec<(&str, AnyEncoder)> = vec![
+            (
+                r#"{"algorithm": "caverphone"}"#,
+                AnyEncoder::Caverphone(Caverphone::default()),
+            ),
+            (r#"{"algorithm": "cologne"}"#, AnyEncoder::Cologne(Cologne)),
+            (
+                r#"{"algorithm": "double_metaphone"}"#,
+                AnyEncoder::DoubleMetaphone(DoubleMetaphone::default()),
+            ),
+            (
+                r#"{"algorithm": "match_rating_approach"}"#,
+                AnyEncoder::MatchRatingApproach(CustomMatchRatingApproach::default()),
+            ),
+            (
+                r#"{"algorithm": "metaphone"}"#,
+                AnyEncoder::Metaphone(Metaphone::default()),
+            ),
+            (
+                r#"{"algorithm": "nysiis"}"#,
+                AnyEncoder::Nysiis(Nysiis::default()),
+            ),
+            (
+                r#"{"algorithm": "phonex"}"#,
+                AnyEncoder::Phonex(Phonex::default()),
+            ),
+            (
+                r#"{"algorithm": "phonix"}"#,
+                AnyEncoder::Phonix(Phonix::default()),
+            ),
+            (
+                r#"{"algorithm": "refined_soundex"}"#,
+                AnyEncoder::RefinedSoundex(RefinedSoundex::default()),
+            ),
+            (
+                r#"{"algorithm": "soundex"}"#,
+                AnyEncoder::Soundex(Soundex::default()),
+            ),
+        ];
+
+        for (json, expected) in data {
+            let config: EncoderConfig = serde_json::from_str(json).unwrap();
+            let encoder = config.build()?;
+            assert_eq!(encoder, expected, "{json}");
+            assert_eq!(encoder.encode("Thompson"), expected.encode("Thompson"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), PhoneticError> {
+        let config = EncoderConfig::DoubleMetaphone {
+            max_code_length: None,
+            respect_internal_spaces: true,
+            silent_letters: BTreeSet::from(['H']),
+            initial_vowel: InitialVowel::Preserve,
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: EncoderConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, config);
+        assert_eq!(deserialized.build()?.encode("Otto Shaw"), "OT S");
+
+        let config = EncoderConfig::Nysiis {
+            strict: true,
+            preserve_initial_vowel: true,
+            max_length: Some(3),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: EncoderConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, config);
+        assert_eq!(deserialized.build()?.encode("Washington"), "WAS");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_any_encoder_round_trip() -> Result<(), PhoneticError> {
+        let data = [
+            AnyEncoder::Soundex(Soundex::default().max_length(6).padding(false)),
+            AnyEncoder::Nysiis(NysiisBuilder::default().strict(false).build()),
+            AnyEncoder::Metaphone(Metaphone::new(None)),
+            AnyEncoder::Caverphone(Caverphone::new(CaverphoneRevision::One)),
+            AnyEncoder::Cologne(Cologne),
+            AnyEncoder::MatchRatingApproach(MatchRatingApproach.with_codex_keep(2)),
+        ];
+        for encoder in data {
+            let json = serde_json::to_string(&encoder).unwrap();
+            let deserialized: AnyEncoder = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, encoder, "{json}");
+            assert_eq!(
+                deserialized.encode("Washington Irving"),
+                encoder.encode("Washington Irving")
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let config: EncoderConfig =
+            serde_json::from_str(r#"{"algorithm": "soundex", "mapping": "0123"}"#).unwrap();
+        assert!(config.build().is_err());
+        let config: EncoderConfig =
+            serde_json::from_str(r#"{"algorithm": "soundex", "max_length": 0}"#).unwrap();
+        assert_eq!(
+            config.build(),
+            Err(PhoneticError::InvalidParameter {
+                name: "max_length".to_string(),
+                reason: "must be greater than 0".to_string(),
+            })
+        );
+        for config in [
+            r#"{"algorithm": "double_metaphone", "max_code_length": 0}"#,
+            r#"{"algorithm": "match_rating_approach", "codex_keep": 0}"#,
+            r#"{"algorithm": "metaphone", "max_code_length": 0}"#,
+            r#"{"algorithm": "nysiis", "max_length": 0}"#,
+            r#"{"algorithm": "phonex", "max_code_length": 0}"#,
+            r#"{"algorithm": "phonix", "max_code_length": 0}"#,
+        ] {
+            let encoder_config: EncoderConfig = serde_json::from_str(config).unwrap();
+            assert!(
+                matches!(
+                    encoder_config.build(),
+                    Err(PhoneticError::InvalidParameter { .. })
+                ),
+                "{config}"
+            );
+        }
+        let config: EncoderConfig =
+            serde_json::from_str(r#"{"algorithm": "refined_soundex", "mapping": "0"}"#).unwrap();
+        assert!(config.build().is_err());
+
+        assert!(serde_json::from_str::<EncoderConfig>(r#"{"algorithm": "klingon"}"#).is_err());
+    }
+
+    #[test]
+    fn test_algorithm() -> Result<(), PhoneticError> {
+        for algorithm in enum_iterator::all::<Algorithm>() {
+            let name = algorithm.to_string();
+            assert_eq!(name.parse::<Algorithm>()?, algorithm);
+            assert_eq!(
+                serde_json::to_string(&algorithm).unwrap(),
+                format!("\"{name}\"")
+            );
+        }
+
+        let data = [
+            (Algorithm::Caverphone1, "RPT111"),
+            (Algorithm::Caverphone2, "RPT1111111"),
+            (Algorithm::Cologne, "7172"),
+            (Algorithm::DoubleMetaphone, "RPRT"),
+            (Algorithm::MatchRatingApproach, "RBRT"),
+            (Algorithm::Metaphone, "RBRT"),
+            (Algorithm::Nysiis, "RABAD"),
+            (Algorithm::Phonex, "R130"),
+            (Algorithm::Phonix, "R130"),
+            (Algorithm::RefinedSoundex, "R901096"),
+            (Algorithm::Soundex, "R163"),
+        ];
+        for (algorithm, expected) in data {
+            assert_eq!(
+                algorithm.encoder().encode("Robert"),
+                expected,
+                "{algorithm}"
+            );
+        }
+
+        assert_eq!(
+            "klingon".parse::<Algorithm>(),
+            Err(PhoneticError::UnknownAlgorithm("klingon".to_string()))
+        );
+        assert!("DoubleMetaphone".parse::<Algorithm>().is_err());
+
+        Ok(())
+    }
+}
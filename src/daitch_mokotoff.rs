@@ -14,39 +14,91 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
 use crate::helper::is_vowel;
 use crate::{
-    build_error, end_of_line, folding, multiline_comment, quadruplet, Encoder, PhoneticError,
+    build_error, end_of_line, folding, multiline_comment, quadruplet, quadruplet_error,
+    BeiderMorse, Encoder, PhoneticError,
 };
 
 #[cfg(feature = "embedded_dm")]
 const DEFAULT_RULES: &str = include_str!("../rules/dmrules.txt");
 
-/// Max length of a DM soundex value.
+/// Default max length of a DM soundex value.
 const MAX_LENGTH: usize = 6;
 
-#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Branch<'a> {
-    builder: String,
+/// Storage of the digits of a [Branch].
+trait Digits: Clone + PartialEq {
+    fn with_capacity(capacity: usize) -> Self;
+
+    fn len(&self) -> usize;
+
+    fn push(&mut self, digit: u8);
+}
+
+impl Digits for String {
+    fn with_capacity(capacity: usize) -> Self {
+        String::with_capacity(capacity)
+    }
+
+    fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn push(&mut self, digit: u8) {
+        self.push(char::from(digit));
+    }
+}
+
+/// Digits of a code of [MAX_LENGTH] digits, that is stored without allocation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct ArrayDigits {
+    digits: [u8; MAX_LENGTH],
+    len: usize,
+}
+
+impl Digits for ArrayDigits {
+    fn with_capacity(_capacity: usize) -> Self {
+        Self {
+            digits: [b'0'; MAX_LENGTH],
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push(&mut self, digit: u8) {
+        if self.len < MAX_LENGTH {
+            self.digits[self.len] = digit;
+            self.len += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Branch<'a, D: Digits> {
+    builder: D,
     last_replacement: Option<&'a str>,
+    max_length: usize,
 }
 
-impl Default for Branch<'_> {
-    fn default() -> Self {
+impl<'a, D: Digits> Branch<'a, D> {
+    fn new(max_length: usize) -> Self {
         Self {
-            builder: String::with_capacity(MAX_LENGTH),
+            builder: D::with_capacity(max_length),
             last_replacement: None,
+            max_length,
         }
     }
-}
 
-impl<'a> Branch<'a> {
-    /// Finish matching [MAX_LENGTH] by appending `0`.
+    /// Finish matching `max_length` by appending `0`.
     fn finish(&mut self) {
-        while self.builder.len() < MAX_LENGTH {
-            self.builder.push('0');
+        while self.builder.len() < self.max_length {
+            self.builder.push(b'0');
         }
     }
 
@@ -56,10 +108,13 @@ impl<'a> Branch<'a> {
             .map_or(true, |v| !v.ends_with(replacement))
             || append_force;
 
-        if append && self.builder.len() < MAX_LENGTH {
-            self.builder.push_str(replacement);
-            if self.builder.len() > MAX_LENGTH {
-                self.builder = self.builder[0..MAX_LENGTH].to_string();
+        if append {
+            // Replacements are ASCII digits, they are truncated to `max_length`
+            for digit in replacement.bytes() {
+                if self.builder.len() >= self.max_length {
+                    break;
+                }
+                self.builder.push(digit);
             }
         }
 
@@ -80,8 +135,9 @@ impl Rule {
         part.split('|').map(|v| v.to_string()).collect()
     }
 
+    /// Length of the pattern, in characters.
     fn get_pattern_length(&self) -> usize {
-        self.pattern.len()
+        self.pattern.chars().count()
     }
 
     fn matches(&self, context: &str) -> bool {
@@ -93,10 +149,12 @@ impl Rule {
             return &self.replacement_at_start;
         }
 
-        let next_index = self.get_pattern_length();
-        let next_char_is_vowel =
-            next_index < context.len() && is_vowel(context.chars().nth(next_index), false);
-        if next_char_is_vowel {
+        // `context` starts with the pattern, so the next character is right after
+        // its bytes.
+        let next_char = context
+            .get(self.pattern.len()..)
+            .and_then(|remains| remains.chars().next());
+        if is_vowel(next_char, false) {
             return &self.replacement_before_vowel;
         }
 
@@ -249,6 +307,8 @@ impl TryFrom<(&str, &str, &str, &str)> for Rule {
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct DaitchMokotoffSoundex {
     ascii_folding: bool,
+    max_length: usize,
+    tokenize: bool,
     rules: BTreeMap<char, Vec<Rule>>,
     ascii_folding_rules: BTreeMap<char, char>,
 }
@@ -283,6 +343,86 @@ impl DaitchMokotoffSoundex {
         self.inner_soundex(value, true).join("|")
     }
 
+    /// Encode the string with branching and return the codes as a set, so they are
+    /// deduplicated and sorted.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode
+    ///
+    /// # Result
+    ///
+    /// The codes of [soundex](DaitchMokotoffSoundex::soundex).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::collections::BTreeSet;
+    /// use rphonetic::DaitchMokotoffSoundexBuilder;
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    ///
+    /// let expected: BTreeSet<String> = ["145460", "154600", "445460", "454600"]
+    ///     .iter()
+    ///     .map(|code| code.to_string())
+    ///     .collect();
+    /// assert_eq!(encoder.soundex_set("Jackson"), expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn soundex_set(&self, value: &str) -> BTreeSet<String> {
+        self.inner_soundex(value, true).into_iter().collect()
+    }
+
+    /// Return `value` as it is seen by the rules: whitespaces are removed, characters are
+    /// lowercased and, if enabled, ASCII folding is applied. This can help to understand
+    /// unexpected codes.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to normalize
+    ///
+    /// # Result
+    ///
+    /// The normalized value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::DaitchMokotoffSoundexBuilder;
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    ///
+    /// assert_eq!(encoder.normalized_input("Stra\u{00DF}burg"), "strasburg");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn normalized_input(&self, value: &str) -> String {
+        value
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .map(|ch| {
+                let lower = ch.to_lowercase().next();
+                match lower {
+                    None => ch,
+                    Some(mut lower) => {
+                        if self.ascii_folding && self.ascii_folding_rules.contains_key(&lower) {
+                            lower = *self.ascii_folding_rules.get(&lower).unwrap();
+                        }
+
+                        lower
+                    }
+                }
+            })
+            .collect::<String>()
+    }
+
     /// Encode a string and return vector of codes avoiding a parsing result
     ///
     /// # Parameters :
@@ -316,25 +456,42 @@ impl DaitchMokotoffSoundex {
     /// # }
     /// ```
     pub fn inner_soundex(&self, value: &str, branching: bool) -> Vec<String> {
-        let source = value
-            .chars()
-            .filter(|ch| !ch.is_whitespace())
-            .map(|ch| {
-                let lower = ch.to_lowercase().next();
-                match lower {
-                    None => ch,
-                    Some(mut lower) => {
-                        if self.ascii_folding && self.ascii_folding_rules.contains_key(&lower) {
-                            lower = *self.ascii_folding_rules.get(&lower).unwrap();
-                        }
+        if !self.tokenize {
+            return self.encode_normalized(&self.normalized_input(value), branching);
+        }
 
-                        lower
-                    }
-                }
+        let tokens: Vec<Vec<String>> = value
+            .split_whitespace()
+            .map(|token| self.encode_normalized(&self.normalized_input(token), branching))
+            .collect();
+        if tokens.is_empty() {
+            // No token, encode as usual
+            return self.encode_normalized("", branching);
+        }
+
+        // Join codes per position rather than building every combination of them,
+        // whose number would grow exponentially with the number of words
+        let count = tokens.iter().map(Vec::len).max().unwrap_or_default();
+        (0..count)
+            .map(|position| {
+                tokens
+                    .iter()
+                    .map(|codes| codes[position.min(codes.len() - 1)].as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" ")
             })
-            .collect::<String>();
+            .collect()
+    }
+
+    /// Encode an already [normalized](DaitchMokotoffSoundex::normalized_input) value.
+    fn encode_normalized(&self, source: &str, branching: bool) -> Vec<String> {
+        self.encode_digits(source, branching, self.max_length)
+    }
 
-        let mut current_branches: Vec<Branch> = vec![Branch::default()];
+    /// Encode an already [normalized](DaitchMokotoffSoundex::normalized_input) value
+    /// into codes of `max_length` digits.
+    fn encode_digits<D: Digits>(&self, source: &str, branching: bool, max_length: usize) -> Vec<D> {
+        let mut current_branches: Vec<Branch<D>> = vec![Branch::new(max_length)];
 
         let mut last_char = '\0';
         let mut iterator = source.char_indices();
@@ -348,7 +505,7 @@ impl DaitchMokotoffSoundex {
             if let Some(rules) = rules {
                 for rule in rules {
                     if rule.matches(context) {
-                        let mut next_branches: Vec<Branch> = Vec::new();
+                        let mut next_branches: Vec<Branch<D>> = Vec::new();
 
                         let replacement = rule.get_replacements(context, last_char == '\0');
 
@@ -375,7 +532,7 @@ impl DaitchMokotoffSoundex {
                         // in a supplementary call.
                         // So we need to "skip" if length >= 2, and we need to substract 2.
                         if l > 1 {
-                            let _ = iterator.nth(rule.get_pattern_length() - 2);
+                            let _ = iterator.nth(l - 2);
                         }
                         break;
                     }
@@ -384,14 +541,134 @@ impl DaitchMokotoffSoundex {
             }
         }
 
-        let mut result: Vec<String> = Vec::with_capacity(current_branches.len());
+        let mut result: Vec<D> = Vec::with_capacity(current_branches.len());
         for branch in current_branches.iter_mut() {
             branch.finish();
-            result.push(branch.builder.clone());
+            // Branches may differ only by their last replacement
+            if !result.contains(&branch.builder) {
+                result.push(branch.builder.clone());
+            }
         }
 
         result
     }
+
+    /// Encode a string with branching and return the lexicographically smallest
+    /// code, so it can be used as a single deterministic key.
+    ///
+    /// This differs from [encode](Encoder::encode): it disables branching and
+    /// always picks the first replacement of each rule, which may not lead to the
+    /// smallest code.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode
+    ///
+    /// # Result
+    ///
+    /// The smallest of the codes returned by [soundex](DaitchMokotoffSoundex::soundex).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::DaitchMokotoffSoundexBuilder;
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    ///
+    /// assert_eq!(encoder.encode_canonical("AUERBACH"), "097400");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn encode_canonical(&self, value: &str) -> String {
+        self.inner_soundex(value, true)
+            .into_iter()
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Encode each name with both this encoder (with branching) and `beider_morse`, for
+    /// instance to study the agreement of both algorithms. `beider_morse` should be built
+    /// with [Ashkenazi](crate::NameType::Ashkenazi) name type, as Daitch-Mokotoff soundex
+    /// was designed for Ashkenazi surnames.
+    ///
+    /// # Parameters
+    ///
+    /// * `beider_morse` : the [BeiderMorse] encoder.
+    /// * `names` : names to encode.
+    ///
+    /// # Result
+    ///
+    /// For each name, a tuple that contains the Daitch-Mokotoff soundex and then
+    /// the Beider-Morse code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::PathBuf;
+    /// use rphonetic::{BeiderMorseBuilder, ConfigFiles, DaitchMokotoffSoundexBuilder, NameType};
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    /// let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+    /// let beider_morse = BeiderMorseBuilder::new(&config_files)
+    ///     .name_type(NameType::Ashkenazi)
+    ///     .build();
+    ///
+    /// let pairs = daitch_mokotoff.cross_validate(&beider_morse, &["Lewinsky"]);
+    /// assert_eq!(pairs[0].0, "876450");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn cross_validate(
+        &self,
+        beider_morse: &BeiderMorse,
+        names: &[&str],
+    ) -> Vec<(String, String)> {
+        names
+            .iter()
+            .map(|name| (self.soundex(name), beider_morse.encode(name)))
+            .collect()
+    }
+
+    /// Encode a string with branching and return each code as a fixed size
+    /// array of ASCII digits, avoiding allocating a [String] per code.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode
+    ///
+    /// # Result
+    ///
+    /// A list of codes. Arrays are always the canonical 6 digits codes of the whole value,
+    /// whatever the [max_length](DaitchMokotoffSoundexBuilder::max_length) and
+    /// [tokenize](DaitchMokotoffSoundexBuilder::tokenize) settings: with default settings,
+    /// they are the codes of [inner_soundex](DaitchMokotoffSoundex::inner_soundex), in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::DaitchMokotoffSoundexBuilder;
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    ///
+    /// assert_eq!(encoder.encode_arrays("LEWINSKY"), vec![*b"876450"]);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn encode_arrays(&self, value: &str) -> Vec<[u8; MAX_LENGTH]> {
+        self.encode_digits::<ArrayDigits>(&self.normalized_input(value), true, MAX_LENGTH)
+            .into_iter()
+            .map(|code| code.digits)
+            .collect()
+    }
 }
 
 impl Encoder for DaitchMokotoffSoundex {
@@ -419,13 +696,58 @@ impl Encoder for DaitchMokotoffSoundex {
             .map(|v| v.to_string())
             .unwrap_or_default()
     }
+
+    /// Check that two strings have at least one branching code in common,
+    /// see [soundex_set](DaitchMokotoffSoundex::soundex_set).
+    ///
+    /// # Example :
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{DaitchMokotoffSoundexBuilder, Encoder};
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    ///
+    /// // "Chaim" is 460000|560000 and "Haim" is 560000
+    /// assert!(!encoder.is_encoded_equals("Chaim", "Haim"));
+    /// assert!(encoder.is_encoded_equals_any("Chaim", "Haim"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    fn is_encoded_equals_any(&self, first: &str, second: &str) -> bool {
+        let first = self.soundex_set(first);
+        let second = self.soundex_set(second);
+
+        !first.is_disjoint(&second)
+    }
+}
+
+/// Parsed rules, by first letter and ordered by pattern length decreasing,
+/// and ASCII folding rules.
+#[derive(Clone, Debug)]
+struct ParsedRules {
+    rules: BTreeMap<char, Vec<Rule>>,
+    ascii_folding_rules: BTreeMap<char, char>,
+}
+
+/// Rules of a [DaitchMokotoffSoundexBuilder], either borrowed and parsed when building
+/// or already parsed from a file.
+#[derive(Clone, Debug)]
+enum BuilderRules<'a> {
+    Text(&'a str),
+    Parsed(ParsedRules),
 }
 
 /// This is a builder for [DaitchMokotoffSoundex].
 #[derive(Clone, Debug)]
 pub struct DaitchMokotoffSoundexBuilder<'a> {
-    rules: &'a str,
+    rules: BuilderRules<'a>,
     ascii_folding: bool,
+    max_length: usize,
+    tokenize: bool,
+    folding: BTreeMap<char, char>,
 }
 
 /// Create a [DaitchMokotoffSoundexBuilder] with
@@ -435,8 +757,11 @@ pub struct DaitchMokotoffSoundexBuilder<'a> {
 impl<'a> Default for DaitchMokotoffSoundexBuilder<'a> {
     fn default() -> Self {
         Self {
-            rules: DEFAULT_RULES,
+            rules: BuilderRules::Text(DEFAULT_RULES),
             ascii_folding: true,
+            max_length: MAX_LENGTH,
+            tokenize: false,
+            folding: BTreeMap::new(),
         }
     }
 }
@@ -445,8 +770,11 @@ impl<'a> DaitchMokotoffSoundexBuilder<'a> {
     /// Create a [DaitchMokotoffSoundexBuilder] with custom rules and `ascii_folding` enable.
     pub fn with_rules(rules: &'a str) -> Self {
         Self {
-            rules,
+            rules: BuilderRules::Text(rules),
             ascii_folding: true,
+            max_length: MAX_LENGTH,
+            tokenize: false,
+            folding: BTreeMap::new(),
         }
     }
 
@@ -457,77 +785,247 @@ impl<'a> DaitchMokotoffSoundexBuilder<'a> {
         self
     }
 
-    /// Construct a new [DaitchMokotoffSoundex] encoder.
+    /// Set the length of codes, they are truncated or padded with `0`
+    /// to this length. Default to 6.
     ///
-    /// # Error
+    /// It doesn't apply to [encode_arrays](DaitchMokotoffSoundex::encode_arrays), that
+    /// always returns 6 digits codes.
     ///
-    /// This method returns an error in case it can't parse the rules.
-    pub fn build(self) -> Result<DaitchMokotoffSoundex, PhoneticError> {
-        let mut rules: BTreeMap<char, Vec<Rule>> = BTreeMap::new();
-        let mut ascii_folding_rules: BTreeMap<char, char> = BTreeMap::new();
-        let mut remains = self.rules;
-        let mut line_number: usize = 0;
-        while !remains.is_empty() {
-            line_number += 1;
-
-            // Parrsing test from more probable to less probable.
-
-            // Try quadruplet rule
-            if let Ok((rm, quadruplet)) = quadruplet()(remains) {
-                let rule = Rule::try_from(quadruplet)?;
-                // There's always at least one char, the regex ensures that.
-                let ch = rule.pattern.chars().next().unwrap();
-                rules.entry(ch).or_default().push(rule);
-                remains = rm;
-                continue;
-            }
+    /// # Panics
+    ///
+    /// It panics if `max_length` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{DaitchMokotoffSoundexBuilder, Encoder};
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+    ///     .max_length(8)
+    ///     .build()?;
+    ///
+    /// assert_eq!(encoder.encode("Rosochowaciec"), "94474400");
+    /// assert_eq!(encoder.encode("Kowalczykowski"), "57845745");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        assert!(max_length > 0, "max_length must be greater than 0");
+        self.max_length = max_length;
 
-            // Try folding rule
-            if let Ok((rm, (pattern, replacement))) = folding()(remains) {
-                ascii_folding_rules.insert(pattern, replacement);
-                remains = rm;
-                continue;
-            }
+        self
+    }
 
-            // Try single line comment
-            if let Ok((rm, _)) = end_of_line()(remains) {
-                remains = rm;
-                continue;
-            }
+    /// Encode each whitespace separated word independently, instead of removing
+    /// whitespaces. Codes of words are then joined with a space. With branching, the
+    /// n-th code joins the n-th code of each word (or its last one if the word has
+    /// fewer codes): there are as many codes as the most branching word has, and each
+    /// code of a word is in at least one of them. Default to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{DaitchMokotoffSoundexBuilder, Encoder};
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+    /// assert_eq!(encoder.encode("de la Cruz"), "384940");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+    ///     .tokenize(true)
+    ///     .build()?;
+    /// assert_eq!(encoder.encode("de la Cruz"), "300000 800000 494000");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn tokenize(mut self, tokenize: bool) -> Self {
+        self.tokenize = tokenize;
 
-            // Try multiline comment
-            if let Ok((rm, ln)) = multiline_comment()(remains) {
-                line_number += ln;
-                remains = rm;
-                continue;
-            }
+        self
+    }
 
-            // Everything fails, then return an error...
-            return Err(build_error(
-                line_number,
-                None,
-                remains,
-                "Can't recognize line".to_string(),
-            ));
-        }
+    /// Add ASCII folding rules, they override those of the rules with the same
+    /// character. This is useful to add transliterations, without writing them
+    /// in the rules format. They are only used if [ascii_folding](DaitchMokotoffSoundexBuilder::ascii_folding)
+    /// is enabled.
+    ///
+    /// # Parameter
+    ///
+    /// * `folding` : map of a lowercase character to its replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::collections::BTreeMap;
+    /// use rphonetic::{DaitchMokotoffSoundexBuilder, Encoder};
+    ///
+    /// const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+    ///     .with_folding(BTreeMap::from([('ð', 'd')]))
+    ///     .build()?;
+    ///
+    /// assert_eq!(encoder.normalized_input("Guðmundsson"), "gudmundsson");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_folding(mut self, folding: BTreeMap<char, char>) -> Self {
+        self.folding.extend(folding);
+
+        self
+    }
+
+    /// Create a [DaitchMokotoffSoundexBuilder] with the rules of a file and
+    /// `ascii_folding` enable. Rules are parsed immediately and owned by the builder so,
+    /// unlike [with_rules](DaitchMokotoffSoundexBuilder::with_rules), the file content
+    /// doesn't need to outlive it.
+    ///
+    /// # Parameter
+    ///
+    /// * `path` : path of the rules file.
+    ///
+    /// # Error
+    ///
+    /// This method returns an error in case it can't read the file or parse the rules.
+    /// In both cases, the [ParseError](crate::ParseError) contains the filename.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use std::path::Path;
+    /// use rphonetic::{DaitchMokotoffSoundexBuilder, Encoder};
+    ///
+    /// let encoder = DaitchMokotoffSoundexBuilder::from_path(Path::new("./rules/dmrules.txt"))?
+    ///     .max_length(8)
+    ///     .build()?;
+    ///
+    /// assert_eq!(encoder.encode("AUERBACH"), "09740000");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn from_path(path: &Path) -> Result<Self, PhoneticError> {
+        let filename = path.display().to_string();
+        let rules = std::fs::read_to_string(path).map_err(|error| {
+            build_error(
+                0,
+                Some(filename.clone()),
+                "",
+                format!("Can't read file : {error}"),
+            )
+        })?;
 
-        // Ordering by pattern length decreasing.
-        rules
-            .values_mut()
-            .for_each(|v| v.sort_by(|a, b| a.pattern.len().cmp(&b.pattern.len()).reverse()));
+        Ok(Self {
+            rules: BuilderRules::Parsed(parse_rules(&rules, Some(filename))?),
+            ascii_folding: true,
+            max_length: MAX_LENGTH,
+            tokenize: false,
+            folding: BTreeMap::new(),
+        })
+    }
+
+    /// Construct a new [DaitchMokotoffSoundex] encoder.
+    ///
+    /// # Error
+    ///
+    /// This method returns an error in case it can't parse the rules.
+    pub fn build(self) -> Result<DaitchMokotoffSoundex, PhoneticError> {
+        let ParsedRules {
+            rules,
+            mut ascii_folding_rules,
+        } = match self.rules {
+            BuilderRules::Text(rules) => parse_rules(rules, None)?,
+            BuilderRules::Parsed(parsed_rules) => parsed_rules,
+        };
+        ascii_folding_rules.extend(self.folding);
 
         Ok(DaitchMokotoffSoundex {
             ascii_folding: self.ascii_folding,
+            max_length: self.max_length,
+            tokenize: self.tokenize,
             rules,
             ascii_folding_rules,
         })
     }
 }
 
+/// Parse the content of a rules file.
+fn parse_rules(rules: &str, filename: Option<String>) -> Result<ParsedRules, PhoneticError> {
+    let mut remains = rules;
+    let mut rules: BTreeMap<char, Vec<Rule>> = BTreeMap::new();
+    let mut ascii_folding_rules: BTreeMap<char, char> = BTreeMap::new();
+    let mut line_number: usize = 0;
+    while !remains.is_empty() {
+        line_number += 1;
+
+        // Parrsing test from more probable to less probable.
+
+        // Try quadruplet rule
+        if let Ok((rm, quadruplet)) = quadruplet()(remains) {
+            let rule = Rule::try_from(quadruplet)?;
+            // There's always at least one char, the regex ensures that.
+            let ch = rule.pattern.chars().next().unwrap();
+            rules.entry(ch).or_default().push(rule);
+            remains = rm;
+            continue;
+        }
+
+        // Try folding rule
+        if let Ok((rm, (pattern, replacement))) = folding()(remains) {
+            ascii_folding_rules.insert(pattern, replacement);
+            remains = rm;
+            continue;
+        }
+
+        // Try single line comment
+        if let Ok((rm, _)) = end_of_line()(remains) {
+            remains = rm;
+            continue;
+        }
+
+        // Try multiline comment
+        if let Ok((rm, ln)) = multiline_comment()(remains) {
+            line_number += ln;
+            remains = rm;
+            continue;
+        }
+
+        // Everything fails, then return an error...
+        return Err(build_error(
+            line_number,
+            filename,
+            remains,
+            quadruplet_error(remains).unwrap_or_else(|| "Can't recognize line".to_string()),
+        ));
+    }
+
+    // Ordering by pattern length decreasing.
+    rules.values_mut().for_each(|v| {
+        v.sort_by(|a, b| {
+            a.get_pattern_length()
+                .cmp(&b.get_pattern_length())
+                .reverse()
+        })
+    });
+
+    Ok(ParsedRules {
+        rules,
+        ascii_folding_rules,
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use super::*;
-    use crate::ParseError;
+    use crate::{BeiderMorseBuilder, ConfigFiles, NameType, ParseError};
 
     const COMMONS_CODEC_RULES: &str = include_str!("../rules/dmrules.txt");
 
@@ -1444,6 +1942,8 @@ mod tests {
 
         let expected = DaitchMokotoffSoundex {
             ascii_folding: true,
+            max_length: MAX_LENGTH,
+            tokenize: false,
             rules,
             ascii_folding_rules,
         };
@@ -1451,7 +1951,7 @@ mod tests {
         let iter1 = result.rules.into_iter().zip(expected.rules);
         for ((ch1, rules1), (ch2, rules2)) in iter1 {
             assert_eq!(ch1, ch2, "Rule key differ");
-            let iter2 = rules1.into_iter().zip(rules2.into_iter());
+            let iter2 = rules1.into_iter().zip(rules2);
             for (rule1, rule2) in iter2 {
                 assert_eq!(rule1, rule2, "Rules differ at key {ch1}");
             }
@@ -1499,6 +1999,8 @@ This rule convert the substring `sh` into
         );
         let expected = DaitchMokotoffSoundex {
             ascii_folding: true,
+            max_length: MAX_LENGTH,
+            tokenize: false,
             rules,
             ascii_folding_rules,
         };
@@ -1547,6 +2049,8 @@ This rule convert the substring `sh` into
         );
         let expected = DaitchMokotoffSoundex {
             ascii_folding: false,
+            max_length: MAX_LENGTH,
+            tokenize: false,
             rules,
             ascii_folding_rules,
         };
@@ -1568,6 +2072,145 @@ This rule convert the substring `sh` into
                 description: "Can't recognize line".to_string(),
             }))
         );
+
+        let result =
+            DaitchMokotoffSoundexBuilder::with_rules("// Comment\n\"a\" \"1\" \"2\" \"3\n").build();
+        assert_eq!(
+            result,
+            Err(PhoneticError::ParseRuleError(ParseError {
+                line_number: 2,
+                filename: None,
+                line_content: "\"a\" \"1\" \"2\" \"3".to_string(),
+                description: "Unterminated quote".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_soundex_set() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        for value in ["Jackson-Jackson", "Rosochowaciec", ""] {
+            let expected: BTreeSet<String> = daitch_mokotoff
+                .soundex(value)
+                .split('|')
+                .map(|code| code.to_string())
+                .collect();
+            assert_eq!(
+                daitch_mokotoff.soundex_set(value),
+                expected,
+                "Error for {value}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_encoded_equals_any() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        // 474000|479400 and 479400|479500
+        assert!(!daitch_mokotoff.is_encoded_equals("Schwarz", "Szwarc"));
+        assert!(daitch_mokotoff.is_encoded_equals_any("Schwarz", "Szwarc"));
+        assert!(daitch_mokotoff.is_encoded_equals_any("Szwarc", "Schwarz"));
+        // 154600|145460|454600|445460 and 154600|454600
+        assert!(daitch_mokotoff.is_encoded_equals_any("Jackson", "Jakson"));
+        // 460000|560000 and 560000
+        assert!(daitch_mokotoff.is_encoded_equals_any("Chaim", "Haim"));
+        assert!(!daitch_mokotoff.is_encoded_equals_any("Chaim", "Peter"));
+        assert!(!daitch_mokotoff.is_encoded_equals_any("Schwarz", "Moskowitz"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_folding() -> Result<(), PhoneticError> {
+        let rules = COMMONS_CODEC_RULES.replace("ø=o", "");
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(&rules).build()?;
+        assert_ne!(
+            daitch_mokotoff.normalized_input("Sørensen"),
+            daitch_mokotoff.normalized_input("Sorensen")
+        );
+
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(&rules)
+            .with_folding(BTreeMap::from([('ø', 'o')]))
+            .build()?;
+        assert_eq!(daitch_mokotoff.normalized_input("Sørensen"), "sorensen");
+        assert_eq!(
+            daitch_mokotoff.soundex("Sørensen"),
+            daitch_mokotoff.soundex("Sorensen")
+        );
+
+        // Override rules
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .with_folding(BTreeMap::from([('ø', 'e')]))
+            .build()?;
+        assert_eq!(daitch_mokotoff.normalized_input("Sørensen"), "serensen");
+
+        // Only with ASCII folding
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(&rules)
+            .with_folding(BTreeMap::from([('ø', 'o')]))
+            .ascii_folding(false)
+            .build()?;
+        assert_eq!(daitch_mokotoff.normalized_input("Sørensen"), "sørensen");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_path() -> Result<(), PhoneticError> {
+        let builder =
+            DaitchMokotoffSoundexBuilder::from_path(&PathBuf::from("./rules/dmrules.txt"))?;
+        let daitch_mokotoff = builder.clone().build()?;
+        assert_eq!(daitch_mokotoff.encode("AUERBACH"), "097400");
+        assert_eq!(
+            daitch_mokotoff,
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?
+        );
+        let daitch_mokotoff = builder
+            .max_length(8)
+            .tokenize(true)
+            .ascii_folding(false)
+            .with_folding(BTreeMap::from([('ð', 'd')]))
+            .build()?;
+        assert_eq!(
+            daitch_mokotoff,
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+                .max_length(8)
+                .tokenize(true)
+                .ascii_folding(false)
+                .with_folding(BTreeMap::from([('ð', 'd')]))
+                .build()?
+        );
+
+        let path = PathBuf::from("./rules/missing.txt");
+        let result = DaitchMokotoffSoundexBuilder::from_path(&path);
+        match result {
+            Err(PhoneticError::ParseRuleError(error)) => {
+                assert_eq!(error.filename, Some(path.display().to_string()));
+                assert!(error.description.starts_with("Can't read file"));
+            }
+            _ => panic!("Expected an error, got {result:?}"),
+        }
+
+        let path = std::env::temp_dir().join("rphonetic_test_from_path_dmrules.txt");
+        std::fs::write(&path, "This is wrong.")?;
+        let result = DaitchMokotoffSoundexBuilder::from_path(&path);
+        std::fs::remove_file(&path)?;
+        assert_eq!(
+            result.err(),
+            Some(PhoneticError::ParseRuleError(ParseError {
+                line_number: 1,
+                filename: Some(path.display().to_string()),
+                line_content: "This is wrong.".to_string(),
+                description: "Can't recognize line".to_string(),
+            }))
+        );
+
+        Ok(())
     }
 
     #[test]
@@ -1748,6 +2391,208 @@ This rule convert the substring `sh` into
         Ok(())
     }
 
+    #[test]
+    fn test_normalized_input() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        assert_eq!(daitch_mokotoff.normalized_input("Straßburg"), "strasburg");
+        assert_eq!(daitch_mokotoff.normalized_input(" Ben Aron "), "benaron");
+        assert_eq!(daitch_mokotoff.normalized_input("Schäfer"), "schafer");
+        assert_eq!(daitch_mokotoff.normalized_input(""), "");
+
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .ascii_folding(false)
+            .build()?;
+        assert_eq!(daitch_mokotoff.normalized_input("Straßburg"), "straßburg");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_canonical() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        assert_eq!(daitch_mokotoff.encode_canonical("AUERBACH"), "097400");
+        assert_eq!(daitch_mokotoff.encode_canonical("Rosochowaciec"), "944744");
+        assert_eq!(daitch_mokotoff.encode_canonical("Rzepka"), "475000");
+        assert_eq!(daitch_mokotoff.encode_canonical(""), "000000");
+
+        // Without branching, the first replacement is not always the smallest
+        assert_eq!(daitch_mokotoff.encode("Jackson"), "154600");
+        assert_eq!(daitch_mokotoff.encode_canonical("Jackson"), "145460");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_branches() -> Result<(), PhoneticError> {
+        let encoder = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        // Two branches give `545000`, they only differ by their last replacement
+        assert_eq!(
+            encoder.soundex("Cchuck"),
+            "450000|445000|455000|454500|545000|544500|550000"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_validate() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+        let config_files = ConfigFiles::new(&PathBuf::from("./test_assets/cc-rules/"))?;
+        let beider_morse = BeiderMorseBuilder::new(&config_files)
+            .name_type(NameType::Ashkenazi)
+            .build();
+        let names = ["Lewinsky", "Rosochowaciec", "Schwarzenegger", "Auerbach"];
+
+        let pairs = daitch_mokotoff.cross_validate(&beider_morse, &names);
+        assert_eq!(pairs.len(), names.len());
+        for (name, (dm_code, bm_code)) in names.iter().zip(pairs.iter()) {
+            assert_eq!(dm_code, &daitch_mokotoff.soundex(name));
+            assert_eq!(bm_code, &beider_morse.encode(name));
+            assert!(!bm_code.is_empty());
+        }
+
+        assert!(daitch_mokotoff
+            .cross_validate(&beider_morse, &[])
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_arrays() -> Result<(), PhoneticError> {
+        let daitch_mokotoff =
+            DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+
+        for value in ["LEWINSKY", "Rosochowaciec", "Jackson-Jackson", ""] {
+            let expected: Vec<Vec<u8>> = daitch_mokotoff
+                .inner_soundex(value, true)
+                .iter()
+                .map(|code| code.as_bytes().to_vec())
+                .collect();
+            let result: Vec<Vec<u8>> = daitch_mokotoff
+                .encode_arrays(value)
+                .iter()
+                .map(|code| code.to_vec())
+                .collect();
+            assert_eq!(result, expected, "Error for {value}");
+        }
+
+        assert_eq!(daitch_mokotoff.encode_arrays("LEWINSKY"), vec![*b"876450"]);
+
+        // Arrays don't depend on length and tokenization
+        let other = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .max_length(4)
+            .tokenize(true)
+            .build()?;
+        for value in ["Rosochowaciec", "Jackson Peters", ""] {
+            assert_eq!(
+                other.encode_arrays(value),
+                daitch_mokotoff.encode_arrays(value),
+                "Error for {value}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_length() -> Result<(), PhoneticError> {
+        let length_6 = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+        let length_8 = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .max_length(8)
+            .build()?;
+
+        assert_eq!(length_6.encode("Rosochowaciec"), "944744");
+        assert_eq!(length_8.encode("Rosochowaciec"), "94474400");
+        assert_eq!(
+            length_6.soundex("Rosochowaciec"),
+            "944744|944745|944754|944755|945744|945745|945754|945755"
+        );
+        assert_eq!(
+            length_8.soundex("Rosochowaciec"),
+            "94474400|94474500|94475400|94475500|94574400|94574500|94575400|94575500"
+        );
+
+        // Longer names are no longer truncated
+        assert_eq!(length_6.encode("Kowalczykowski"), "578457");
+        assert_eq!(length_8.encode("Kowalczykowski"), "57845745");
+
+        // Branches leading to the same code are deduplicated
+        let length_4 = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .max_length(4)
+            .build()?;
+        assert_eq!(length_4.soundex("Rosochowaciec"), "9447|9457");
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_length_zero() {
+        let _ = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).max_length(0);
+    }
+
+    #[test]
+    fn test_tokenize() -> Result<(), PhoneticError> {
+        let glued = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES).build()?;
+        let tokenized = DaitchMokotoffSoundexBuilder::with_rules(COMMONS_CODEC_RULES)
+            .tokenize(true)
+            .build()?;
+
+        assert_eq!(
+            glued.encode("Jackson Peters"),
+            glued.encode("JacksonPeters")
+        );
+        assert_eq!(tokenized.encode("Jackson Peters"), "154600 734000");
+        assert_eq!(
+            tokenized.soundex("Jackson  Peters"),
+            "154600 734000|145460 739400|454600 739400|445460 739400"
+        );
+        for code in tokenized.inner_soundex("Jackson Peters", true) {
+            let (jackson, peters) = code.split_once(' ').unwrap();
+            assert!(glued.soundex("Jackson").split('|').any(|c| c == jackson));
+            assert!(glued.soundex("Peters").split('|').any(|c| c == peters));
+        }
+
+        // The number of codes doesn't grow with the number of words
+        let codes = tokenized.inner_soundex("Jackson Jackson Jackson Jackson", true);
+        assert_eq!(codes.len(), 4);
+        assert_eq!(codes[1], "145460 145460 145460 145460");
+
+        // Single word and no word
+        assert_eq!(tokenized.soundex("Jackson"), glued.soundex("Jackson"));
+        assert_eq!(tokenized.encode(""), glued.encode(""));
+        assert_eq!(tokenized.encode("  "), glued.encode("  "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multibyte_pattern() -> Result<(), PhoneticError> {
+        let rules = r#"
+"țs" "9" "9" "9"
+"ţ" "1" "2" "3"
+"a" "0" "" ""
+"b" "7" "7" "7"
+"m" "6" "6" "6"
+"#;
+        let daitch_mokotoff = DaitchMokotoffSoundexBuilder::with_rules(rules).build()?;
+
+        // The character after a 2 characters pattern is not skipped
+        assert_eq!(daitch_mokotoff.encode("țsbam"), "976000");
+        // Next character of a multibyte pattern is a vowel
+        assert_eq!(daitch_mokotoff.encode("bţa"), "720000");
+        assert_eq!(daitch_mokotoff.encode("bţm"), "736000");
+
+        Ok(())
+    }
+
     #[test]
     fn test_special_romanian_characters() -> Result<(), PhoneticError> {
         let daitch_mokotoff =
@@ -14,9 +14,14 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::fmt::{Display, Formatter};
-use std::iter::Peekable;
-use std::str::CharIndices;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::iter::Peekable;
+use core::str::CharIndices;
 
 use serde::{Deserialize, Serialize};
 
@@ -40,7 +45,7 @@ pub struct DoubleMetaphoneResult {
 }
 
 impl Display for DoubleMetaphoneResult {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "[primary={}, alternate={}]",
@@ -73,6 +78,99 @@ impl DoubleMetaphoneResult {
         self.alternate.clone()
     }
 
+    /// Return the `primary` code without copying it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let result = DoubleMetaphone::default().double_metaphone("jumped");
+    ///
+    /// assert_eq!(result.primary_str(), "JMPT");
+    /// ```
+    pub fn primary_str(&self) -> &str {
+        &self.primary
+    }
+
+    /// Return the `alternate` code without copying it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let result = DoubleMetaphone::default().double_metaphone("jumped");
+    ///
+    /// assert_eq!(result.alternate_str(), "AMPT");
+    /// ```
+    pub fn alternate_str(&self) -> &str {
+        &self.alternate
+    }
+
+    /// Consume the result and return both `primary` and `alternate` codes,
+    /// without copying them.
+    ///
+    /// # Return
+    ///
+    /// A tuple `(primary, alternate)`.
+    pub fn into_codes(self) -> (String, String) {
+        (self.primary, self.alternate)
+    }
+
+    /// Merge this result with `other`, for instance to combine the codes of the
+    /// first name and the last name.
+    ///
+    /// Both `primary` codes are joined with `sep`, and so are both `alternate` codes.
+    /// Codes are not truncated again: each part keeps the length it was encoded with,
+    /// and the maximum length of the merged result is the sum of both maximum lengths
+    /// plus the separator (or no maximum if one of the parts has none). If one of the
+    /// codes to join is empty, the separator is omitted.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` : result to append after this one.
+    /// * `sep` : separator between both codes.
+    ///
+    /// # Return
+    ///
+    /// The merged result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    /// let first_name = double_metaphone.double_metaphone("John");
+    /// let last_name = double_metaphone.double_metaphone("Smith");
+    ///
+    /// let result = first_name.concat(&last_name, ' ');
+    ///
+    /// assert_eq!(result.primary_str(), "JN SM0");
+    /// assert_eq!(result.alternate_str(), "AN XMT");
+    /// ```
+    pub fn concat(&self, other: &DoubleMetaphoneResult, sep: char) -> DoubleMetaphoneResult {
+        fn join(first: &str, second: &str, sep: char) -> String {
+            let mut result = String::with_capacity(first.len() + sep.len_utf8() + second.len());
+            result.push_str(first);
+            if !first.is_empty() && !second.is_empty() {
+                result.push(sep);
+            }
+            result.push_str(second);
+            result
+        }
+
+        Self {
+            primary: join(&self.primary, &other.primary, sep),
+            alternate: join(&self.alternate, &other.alternate, sep),
+            max_length: self
+                .max_length
+                .zip(other.max_length)
+                .map(|(first, second)| first + sep.len_utf8() + second),
+        }
+    }
+
     fn append_char(&mut self, ch: char, alternate: Option<char>) {
         self.append_char_primary(ch);
         self.append_char_alternate(alternate.unwrap_or(ch));
@@ -139,6 +237,36 @@ impl DoubleMetaphoneResult {
     }
 }
 
+/// This enum tells [DoubleMetaphone] what to do with a vowel (`A`, `E`, `I`, `O`, `U` or `Y`)
+/// that starts the value. Other vowels are always skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{DoubleMetaphone, Encoder, InitialVowel};
+///
+/// let double_metaphone = DoubleMetaphone::default();
+/// assert_eq!(double_metaphone.encode("Otto"), "AT");
+///
+/// let double_metaphone = DoubleMetaphone::default().initial_vowel(InitialVowel::Preserve);
+/// assert_eq!(double_metaphone.encode("Otto"), "OT");
+///
+/// let double_metaphone = DoubleMetaphone::default().initial_vowel(InitialVowel::Drop);
+/// assert_eq!(double_metaphone.encode("Otto"), "T");
+/// ```
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum InitialVowel {
+    /// Initial vowel is encoded as `A` (this is the default, as in commons-codec).
+    #[default]
+    AlwaysA,
+    /// Initial vowel is kept as is.
+    Preserve,
+    /// Initial vowel is skipped like any other vowel.
+    Drop,
+}
+
 /// This is the [Double Metaphone](https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone) implementation.
 ///
 /// The [Default] implementation has a maximum code length of 4.
@@ -160,13 +288,108 @@ impl DoubleMetaphoneResult {
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct DoubleMetaphone {
     max_code_length: Option<usize>,
+    #[serde(default)]
+    respect_internal_spaces: bool,
+    /// Letters `A..Z` that are removed before encoding, bit 0 is `A`.
+    #[serde(default)]
+    silent_letters: u32,
+    #[serde(default)]
+    initial_vowel: InitialVowel,
+    #[serde(default = "default_true")]
+    trim: bool,
+    #[serde(default = "default_true")]
+    uppercase: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for DoubleMetaphone {
     /// Construct a new [DoubleMetaphone] with a maximum code length of 4.
+    fn default() -> Self {
+        Self::new(Some(4))
+    }
+}
+
+/// This is a builder for [DoubleMetaphone].
+///
+/// Its [Default] implementation has a maximum code length of 4, as [DoubleMetaphone]'s,
+/// and values are trimmed and uppercased before encoding.
+///
+/// Trimming and uppercasing can be disabled if values are already normalized.
+/// Disabling uppercasing is only correct for uppercase values.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{DoubleMetaphoneBuilder, Encoder};
+///
+/// let double_metaphone = DoubleMetaphoneBuilder::default()
+///     .trim(false)
+///     .uppercase(false)
+///     .build();
+///
+/// assert_eq!(double_metaphone.encode("JUMPED"), "JMPT");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DoubleMetaphoneBuilder {
+    max_code_length: Option<usize>,
+    trim: bool,
+    uppercase: bool,
+}
+
+impl Default for DoubleMetaphoneBuilder {
     fn default() -> Self {
         Self {
             max_code_length: Some(4),
+            trim: true,
+            uppercase: true,
+        }
+    }
+}
+
+impl DoubleMetaphoneBuilder {
+    /// Set the maximum code length.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_code_length`: the maximum code length. If you provide [Option::None]
+    ///   then the resulting code can be of any length.
+    pub fn max_code_length(mut self, max_code_length: Option<usize>) -> Self {
+        self.max_code_length = max_code_length;
+
+        self
+    }
+
+    /// Enable or disable trimming values before encoding. It is enabled by default.
+    ///
+    /// # Parameter
+    ///
+    /// * `trim`: if `false`, values are encoded as is.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+
+        self
+    }
+
+    /// Enable or disable uppercasing values before encoding. It is enabled by default.
+    ///
+    /// # Parameter
+    ///
+    /// * `uppercase`: if `false`, values must already be uppercased.
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+
+        self
+    }
+
+    /// Construct a new [DoubleMetaphone] encoder.
+    pub fn build(self) -> DoubleMetaphone {
+        DoubleMetaphone {
+            trim: self.trim,
+            uppercase: self.uppercase,
+            ..DoubleMetaphone::new(self.max_code_length)
         }
     }
 }
@@ -179,7 +402,122 @@ impl DoubleMetaphone {
     /// * `max_code_length`: the maximum code length. If you provide [Option::None]
     ///   then the resulting code can be of any length.
     pub fn new(max_code_length: Option<usize>) -> Self {
-        Self { max_code_length }
+        Self {
+            max_code_length,
+            respect_internal_spaces: false,
+            silent_letters: 0,
+            initial_vowel: InitialVowel::default(),
+            trim: true,
+            uppercase: true,
+        }
+    }
+
+    /// Set how a vowel that starts the value is encoded. By default, it is
+    /// encoded as `A`, see [InitialVowel].
+    ///
+    /// # Parameter
+    ///
+    /// * `initial_vowel`: what to do with the initial vowel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{DoubleMetaphone, Encoder, InitialVowel};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default().initial_vowel(InitialVowel::Preserve);
+    ///
+    /// assert_eq!(double_metaphone.encode("Ewell"), "EL");
+    /// ```
+    pub fn initial_vowel(mut self, initial_vowel: InitialVowel) -> Self {
+        self.initial_vowel = initial_vowel;
+        self
+    }
+
+    /// Set letters that are silent wherever they appear. They are removed from the
+    /// value before encoding, in addition to the silent starts (eg. `KN` or `PS`).
+    /// Letters are case-insensitive. Only ASCII letters are supported, other chars
+    /// are ignored. By default, there's no silent letter.
+    ///
+    /// # Parameter
+    ///
+    /// * `silent_letters`: letters to skip.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::BTreeSet;
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    /// assert_eq!(double_metaphone.encode("Shaw"), "X");
+    ///
+    /// let double_metaphone = double_metaphone.with_silent_letters(&BTreeSet::from(['h']));
+    /// assert_eq!(double_metaphone.encode("Shaw"), "S");
+    /// ```
+    pub fn with_silent_letters(mut self, silent_letters: &BTreeSet<char>) -> Self {
+        self.silent_letters = silent_letters
+            .iter()
+            .filter(|ch| ch.is_ascii_alphabetic())
+            .fold(0, |mask, ch| {
+                mask | 1 << (ch.to_ascii_uppercase() as u32 - 'A' as u32)
+            });
+
+        self
+    }
+
+    /// Remove silent letters from an uppercased `value`.
+    fn remove_silent_letters<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.silent_letters == 0 || !value.chars().any(|ch| self.is_silent(ch)) {
+            return Cow::Borrowed(value);
+        }
+
+        Cow::Owned(value.chars().filter(|ch| !self.is_silent(*ch)).collect())
+    }
+
+    fn is_silent(&self, ch: char) -> bool {
+        ch.is_ascii_uppercase() && self.silent_letters & 1 << (ch as u32 - 'A' as u32) != 0
+    }
+
+    /// Trim `value`, unless it is disabled.
+    fn trim<'a>(&self, value: &'a str) -> &'a str {
+        if self.trim {
+            value.trim()
+        } else {
+            value
+        }
+    }
+
+    /// Uppercase `value`, unless it is disabled.
+    fn uppercase<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.uppercase {
+            Cow::Owned(value.to_uppercase())
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Enable or disable encoding each word separately. By default, it is disabled and
+    /// spaces are part of the value.
+    ///
+    /// When enabled, each word is encoded on its own, as if it was the whole value
+    /// (maximum code length applies to each word), and codes are joined with a space.
+    ///
+    /// # Parameter
+    ///
+    /// * `respect_internal_spaces`: if `true`, words are encoded separately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default().respect_internal_spaces(true);
+    ///
+    /// assert_eq!(double_metaphone.encode("mac caffrey"), "MK KFR");
+    /// ```
+    pub fn respect_internal_spaces(mut self, respect_internal_spaces: bool) -> Self {
+        self.respect_internal_spaces = respect_internal_spaces;
+        self
     }
 
     /// This method encode and return the alternate code.
@@ -195,6 +533,123 @@ impl DoubleMetaphone {
         self.double_metaphone(value).alternate
     }
 
+    /// Encode `value` and return both `primary` and `alternate` codes. Unlike calling
+    /// [Encoder::encode] and [DoubleMetaphone::encode_alternate], the value is processed
+    /// only once.
+    ///
+    /// This is the same as [DoubleMetaphone::double_metaphone].
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Result
+    ///
+    /// A [DoubleMetaphoneResult] that contains both `primary` and `alternate` code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// let result = double_metaphone.encode_both("jumped");
+    /// assert_eq!(result.primary_str(), "JMPT");
+    /// assert_eq!(result.alternate_str(), "AMPT");
+    /// ```
+    pub fn encode_both(&self, value: &str) -> DoubleMetaphoneResult {
+        self.double_metaphone(value)
+    }
+
+    /// Encode each value of `values` and return their [DoubleMetaphoneResult], in the same order.
+    ///
+    /// # Parameter
+    ///
+    /// * `values` : values to encode.
+    ///
+    /// # Result
+    ///
+    /// A [DoubleMetaphoneResult] for each value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// let results = double_metaphone.double_metaphone_all(&["jumped", "Smith"]);
+    /// assert_eq!(results[0].primary_str(), "JMPT");
+    /// assert_eq!(results[1].alternate_str(), "XMT");
+    /// ```
+    pub fn double_metaphone_all(&self, values: &[&str]) -> Vec<DoubleMetaphoneResult> {
+        values
+            .iter()
+            .map(|value| self.double_metaphone(value))
+            .collect()
+    }
+
+    /// Encode `value` and return both `primary` and `alternate` codes as a tuple.
+    /// The value is processed only once.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Result
+    ///
+    /// A tuple `(primary, alternate)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// assert_eq!(
+    ///     double_metaphone.encode_pair("jumped"),
+    ///     ("JMPT".to_string(), "AMPT".to_string())
+    /// );
+    /// ```
+    pub fn encode_pair(&self, value: &str) -> (String, String) {
+        self.double_metaphone(value).into_codes()
+    }
+
+    /// Encode `value` and return the `primary` code where internal markers are
+    /// replaced by IPA-like symbols: `0` (theta) becomes `θ` and `X` (`sh`/`ch`)
+    /// becomes `ʃ`. Other characters are kept as is.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Result
+    ///
+    /// The rendered `primary` code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// assert_eq!(double_metaphone.encode("Smith"), "SM0");
+    /// assert_eq!(double_metaphone.render_ipa("Smith"), "SM\u{03B8}");
+    /// ```
+    pub fn render_ipa(&self, value: &str) -> String {
+        self.encode(value)
+            .chars()
+            .map(|ch| match ch {
+                '0' => 'θ',
+                'X' => 'ʃ',
+                _ => ch,
+            })
+            .collect()
+    }
+
     /// This method check if code generated by `value1` and `value2` are equals.
     ///
     /// # Parameters
@@ -215,6 +670,192 @@ impl DoubleMetaphone {
         }
     }
 
+    /// Encode `value` and return the `primary` code where each character is
+    /// annotated with the index (in char) of the character of `value` that produced it.
+    ///
+    /// Indexes are computed after uppercasing `value`, so they might be shifted if
+    /// uppercasing changes the number of characters (eg. `ß` becomes `SS`).
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : value to encode.
+    ///
+    /// # Result
+    ///
+    /// The characters of the `primary` code along with their source index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// let annotated = double_metaphone.encode_annotated("jumped");
+    /// assert_eq!(annotated, vec![(0, 'J'), (2, 'M'), (3, 'P'), (5, 'T')]);
+    ///
+    /// let code: String = annotated.iter().map(|(_, ch)| ch).collect();
+    /// assert_eq!(code, double_metaphone.encode("jumped"));
+    /// ```
+    pub fn encode_annotated(&self, value: &str) -> Vec<(usize, char)> {
+        let mut annotations = Vec::new();
+        let offset = if self.trim {
+            value.chars().take_while(|c| c.is_whitespace()).count()
+        } else {
+            0
+        };
+        let value = self.trim(value);
+        if value.is_empty() {
+            return annotations;
+        }
+
+        let value = &*self.uppercase(value);
+
+        // Silent letters are removed, so keep the position (in char) of each remaining
+        // character in `value`.
+        let remaining: Vec<(usize, char)> = value
+            .chars()
+            .enumerate()
+            .filter(|(_, ch)| !self.is_silent(*ch))
+            .skip_while(|(_, ch)| self.trim && ch.is_whitespace())
+            .collect();
+        let end = if self.trim {
+            remaining
+                .iter()
+                .rposition(|(_, ch)| !ch.is_whitespace())
+                .map(|end| end + 1)
+                .unwrap_or(0)
+        } else {
+            remaining.len()
+        };
+        let positions: Vec<usize> = remaining[..end]
+            .iter()
+            .map(|(position, _)| offset + position)
+            .collect();
+        let value: &str = &remaining[..end]
+            .iter()
+            .map(|(_, ch)| ch)
+            .collect::<String>();
+        if value.is_empty() {
+            return annotations;
+        }
+
+        if self.respect_internal_spaces {
+            for (index, word) in value.split_whitespace().enumerate() {
+                // Words are sub-slices of `value`
+                let start = value[..word.as_ptr() as usize - value.as_ptr() as usize]
+                    .chars()
+                    .count();
+                let mut word_annotations = Vec::new();
+                self.inner_double_metaphone(
+                    word,
+                    Self::is_slavo_germanic(word),
+                    Some(&mut word_annotations),
+                );
+                if index > 0 {
+                    annotations.push((start - 1, ' '));
+                }
+                annotations.extend(
+                    word_annotations
+                        .into_iter()
+                        .map(|(position, c)| (start + position, c)),
+                );
+            }
+        } else {
+            let slavo_germanic = Self::is_slavo_germanic(value);
+            self.inner_double_metaphone(value, slavo_germanic, Some(&mut annotations));
+        }
+
+        annotations
+            .iter_mut()
+            .for_each(|(position, _)| *position = positions[*position]);
+
+        annotations
+    }
+
+    /// This method check if the code of `query` matches a precomputed code pair.
+    /// Only `query` is encoded, then both of its codes are compared with both
+    /// stored codes.
+    ///
+    /// # Parameters
+    ///
+    /// * `query`: value to encode.
+    /// * `stored_primary` and `stored_alternate`: precomputed codes.
+    ///
+    /// # Result
+    ///
+    /// Return `true` if one of `query`'s codes is equal to one of the stored codes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// assert!(double_metaphone.matches_codes("Schmidt", "SM0", "XMT"));
+    /// assert!(!double_metaphone.matches_codes("Jones", "SM0", "XMT"));
+    /// ```
+    pub fn matches_codes(&self, query: &str, stored_primary: &str, stored_alternate: &str) -> bool {
+        let result = self.double_metaphone(query);
+        Self::codes_match(&result, stored_primary, stored_alternate)
+    }
+
+    /// Compute, for each pair of `names`, if one of the codes of the first name is equal to
+    /// one of the codes of the second name.
+    ///
+    /// Each name is encoded only once: codes are cached and then compared, so it
+    /// avoids re-encoding names for each pair.
+    ///
+    /// # Parameter
+    ///
+    /// * `names`: names to compare.
+    ///
+    /// # Result
+    ///
+    /// A symmetric matrix where `matrix[i][j]` is `true` if `names[i]` and `names[j]` have
+    /// a code in common.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::DoubleMetaphone;
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// let matrix = double_metaphone.equality_matrix(&["Smith", "Schmidt", "Jones"]);
+    /// assert_eq!(
+    ///     matrix,
+    ///     vec![
+    ///         vec![true, true, false],
+    ///         vec![true, true, false],
+    ///         vec![false, false, true]
+    ///     ]
+    /// );
+    /// ```
+    pub fn equality_matrix(&self, names: &[&str]) -> Vec<Vec<bool>> {
+        let results: Vec<DoubleMetaphoneResult> = names
+            .iter()
+            .map(|name| self.double_metaphone(name))
+            .collect();
+
+        results
+            .iter()
+            .map(|result1| {
+                results
+                    .iter()
+                    .map(|result2| Self::codes_match(result1, &result2.primary, &result2.alternate))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn codes_match(result: &DoubleMetaphoneResult, primary: &str, alternate: &str) -> bool {
+        [result.primary.as_str(), result.alternate.as_str()]
+            .iter()
+            .any(|code| code == &primary || code == &alternate)
+    }
+
     fn is_slavo_germanic(value: &str) -> bool {
         value.chars().any(|c| c == 'W' || c == 'K')
             || value.contains("CZ")
@@ -274,16 +915,52 @@ impl DoubleMetaphone {
     ///
     /// A [DoubleMetaphone] that contains both `primary` and `alternate` code.
     pub fn double_metaphone(&self, value: &str) -> DoubleMetaphoneResult {
-        let mut result = DoubleMetaphoneResult::new(self.max_code_length);
-        let value = value.trim();
+        let value = self.trim(value);
         if value.is_empty() {
-            return result;
+            return DoubleMetaphoneResult::new(self.max_code_length);
+        }
+
+        let value = &*self.uppercase(value);
+        let value = &*self.remove_silent_letters(value);
+        let value = self.trim(value);
+        if value.is_empty() {
+            return DoubleMetaphoneResult::new(self.max_code_length);
         }
 
-        let value = &value.to_uppercase();
+        if self.respect_internal_spaces {
+            let mut result = DoubleMetaphoneResult::new(None);
+            for (index, word) in value.split_whitespace().enumerate() {
+                let slavo_germanic = Self::is_slavo_germanic(word);
+                let (word_result, _) = self.inner_double_metaphone(word, slavo_germanic, None);
+                if index > 0 {
+                    result.append_char(' ', None);
+                }
+                result.append_str(&word_result.primary, Some(&word_result.alternate));
+            }
+            result.max_length = self.max_code_length;
+            return result;
+        }
 
         let slavo_germanic = Self::is_slavo_germanic(value);
 
+        self.inner_double_metaphone(value, slavo_germanic, None).0
+    }
+
+    /// Encode an already trimmed and uppercased `value`.
+    ///
+    /// It returns the result and the byte index of the first character that
+    /// was not processed (`value.len()` if all the characters were processed).
+    ///
+    /// If `annotations` is provided, each character appended to the primary
+    /// code is pushed along with the index (in char) of the character it comes from.
+    fn inner_double_metaphone(
+        &self,
+        value: &str,
+        slavo_germanic: bool,
+        mut annotations: Option<&mut Vec<(usize, char)>>,
+    ) -> (DoubleMetaphoneResult, usize) {
+        let mut result = DoubleMetaphoneResult::new(self.max_code_length);
+
         let mut iterator: Peekable<CharIndices<'_>> = value.char_indices().peekable();
         let mut char_index: Option<(usize, char)> = iterator.next();
         if SILENT_START.iter().any(|sl| value.starts_with(sl)) {
@@ -291,12 +968,17 @@ impl DoubleMetaphone {
         }
         while !result.is_complete() && char_index.is_some() {
             let (index, ch) = char_index.unwrap();
+            let primary_length = result.primary.len();
             let index = index as isize;
 
             let skip = match ch {
                 'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
                     if index == 0 {
-                        result.append_char('A', None);
+                        match self.initial_vowel {
+                            InitialVowel::AlwaysA => result.append_char('A', None),
+                            InitialVowel::Preserve => result.append_char(ch, None),
+                            InitialVowel::Drop => (),
+                        }
                     }
                     0
                 }
@@ -380,10 +1062,20 @@ impl DoubleMetaphone {
                 _ => 0,
             };
 
+            if let Some(annotations) = annotations.as_mut() {
+                let position = value[..index as usize].chars().count();
+                annotations.extend(
+                    result.primary[primary_length..]
+                        .chars()
+                        .map(|c| (position, c)),
+                );
+            }
+
             char_index = iterator.nth(skip);
         }
 
-        result
+        let stop = char_index.map(|(index, _)| index).unwrap_or(value.len());
+        (result, stop)
     }
 
     fn handle_c(value: &str, result: &mut DoubleMetaphoneResult, index: isize) -> usize {
@@ -507,7 +1199,7 @@ impl DoubleMetaphone {
             || (index > 1 && Self::contains(value, index + 2, 1, vec!["T", "S"]))
             || ((index == 0 || Self::contains(value, index - 1, 1, vec!["A", "O", "U", "E"]))
                 && (Self::contains_array(value, index + 2, 1, L_R_N_M_B_H_F_V_W_SPACE)
-                    || (index as usize) + 1 == value.len() - 1))
+                    || value.len().checked_sub(2) == Some(index as usize)))
     }
 
     fn handle_cc(value: &str, result: &mut DoubleMetaphoneResult, index: isize) -> usize {
@@ -705,7 +1397,7 @@ impl DoubleMetaphone {
                     || Self::char_at(value, index + 1) == Some('O'))
             {
                 result.append_char('J', Some('H'));
-            } else if (index as usize) == value.len() - 1 {
+            } else if value.len().checked_sub(1) == Some(index as usize) {
                 result.append_char('J', Some(' '));
             } else if !Self::contains_array(value, index + 1, 1, L_T_K_S_N_M_B_Z)
                 && (index == 0 || !Self::contains(value, index - 1, 1, vec!["S", "K", "L"]))
@@ -736,7 +1428,7 @@ impl DoubleMetaphone {
     }
 
     fn condition_l0(value: &str, index: isize) -> bool {
-        if (index as usize) == value.len() - 3
+        if value.len().checked_sub(3) == Some(index as usize)
             && index > 0
             && Self::contains(value, index - 1, 4, vec!["ILLO", "ILLA", "ALLE"])
         {
@@ -746,7 +1438,7 @@ impl DoubleMetaphone {
         ((value.len() > 1 && Self::contains(value, value.len() as isize - 2, 2, vec!["AS", "OS"]))
             || (!value.is_empty()
                 && Self::contains(value, value.len() as isize - 1, 1, vec!["A", "O"])))
-            && !value.is_empty()
+            && index > 0
             && Self::contains(value, index - 1, 4, vec!["ALLE"])
     }
 
@@ -1037,9 +1729,173 @@ impl Encoder for DoubleMetaphone {
     }
 }
 
+/// Number of bytes after the last processed character that [DoubleMetaphone]
+/// may look at to encode it.
+const STREAM_LOOKAHEAD: usize = 6;
+
+/// This is a streaming version of [DoubleMetaphone]. It allows to encode a value
+/// that is provided through multiple chunks.
+///
+/// Chunks are uppercased and buffered until the code is complete, that is until
+/// the maximum code length is reached and at least 6 bytes of lookahead after
+/// the last processed character are available. The whole beginning of the value is
+/// kept as lookbehind, so there is no limit on it. Once complete, the following
+/// chunks are no longer buffered : only the last two characters and
+/// [slavo-germanic](https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone)
+/// markers are retained, as they can still change the result.
+///
+/// If [DoubleMetaphone] has no maximum code length, or encodes each word separately,
+/// the code is never complete, so the whole value is buffered.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{DoubleMetaphone, DoubleMetaphoneStream};
+///
+/// let mut stream = DoubleMetaphoneStream::new(DoubleMetaphone::default());
+/// stream.push("ju");
+/// stream.push("mp");
+/// stream.push("ed");
+///
+/// let result = stream.finish();
+/// assert_eq!(result.primary(), "JMPT");
+/// assert_eq!(result.alternate(), "AMPT");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DoubleMetaphoneStream {
+    double_metaphone: DoubleMetaphone,
+    buffer: String,
+    complete: bool,
+    slavo_germanic: bool,
+    previous: Option<char>,
+    tail: String,
+    trailing_content: bool,
+}
+
+impl DoubleMetaphoneStream {
+    /// Construct a new stream that will encode with `double_metaphone`.
+    ///
+    /// # Parameter
+    ///
+    /// * `double_metaphone`: the encoder to use.
+    pub fn new(double_metaphone: DoubleMetaphone) -> Self {
+        Self {
+            double_metaphone,
+            buffer: String::new(),
+            complete: false,
+            slavo_germanic: false,
+            previous: None,
+            tail: String::with_capacity(2),
+            trailing_content: false,
+        }
+    }
+
+    /// Feed the stream with the next chunk of the value.
+    ///
+    /// # Parameter
+    ///
+    /// * `chunk`: next part of the value to encode.
+    pub fn push(&mut self, chunk: &str) {
+        let trim = self.double_metaphone.trim;
+        let chunk = self.double_metaphone.uppercase(chunk);
+        for ch in chunk.chars() {
+            if self.double_metaphone.is_silent(ch) {
+                continue;
+            }
+            // Leading whitespaces are trimmed, if the encoder trims values
+            if trim && self.buffer.is_empty() && ch.is_whitespace() {
+                continue;
+            }
+            if ch == 'W' || ch == 'K' || (ch == 'Z' && self.previous == Some('C')) {
+                self.slavo_germanic = true;
+            }
+            // Without trimming, trailing whitespaces are part of the ending
+            if !trim || !ch.is_whitespace() {
+                self.tail.clear();
+                if let Some(previous) = self.previous {
+                    self.tail.push(previous);
+                }
+                self.tail.push(ch);
+            }
+            self.previous = Some(ch);
+
+            if self.complete {
+                self.trailing_content |= !trim || !ch.is_whitespace();
+            } else {
+                self.buffer.push(ch);
+            }
+        }
+
+        if !self.complete {
+            self.check_complete();
+        }
+    }
+
+    /// Finish the stream and return the result.
+    ///
+    /// # Return
+    ///
+    /// The [DoubleMetaphoneResult] of the whole value.
+    pub fn finish(self) -> DoubleMetaphoneResult {
+        if !self.trailing_content {
+            return self.double_metaphone.double_metaphone(&self.buffer);
+        }
+
+        // Some characters were dropped: the last ones are appended so that
+        // checks on the end of the value are still correct.
+        let mut value = self.buffer;
+        value.push_str(&self.tail);
+        self.double_metaphone
+            .inner_double_metaphone(&value, self.slavo_germanic, None)
+            .0
+    }
+
+    /// Check if the buffer is sufficient to compute the code whatever
+    /// the next chunks are, and if so, truncate it.
+    fn check_complete(&mut self) {
+        if self.double_metaphone.max_code_length.is_none()
+            || self.double_metaphone.respect_internal_spaces
+        {
+            return;
+        }
+
+        // Next chunks may still make the value slavo-germanic and change its
+        // ending, so every possibility must lead to a complete code.
+        let mut slavo_germanic = vec![true];
+        if !self.slavo_germanic {
+            slavo_germanic.push(false);
+        }
+        let mut max_stop = 0;
+        for slavo_germanic in slavo_germanic {
+            for ending in ["A", "B"] {
+                let mut value = self.buffer.clone();
+                value.push_str(ending);
+                let (result, stop) =
+                    self.double_metaphone
+                        .inner_double_metaphone(&value, slavo_germanic, None);
+                if !result.is_complete() || stop + STREAM_LOOKAHEAD > self.buffer.len() {
+                    return;
+                }
+                max_stop = max_stop.max(stop);
+            }
+        }
+
+        let mut length = max_stop + STREAM_LOOKAHEAD;
+        while !self.buffer.is_char_boundary(length) {
+            length += 1;
+        }
+        self.buffer.truncate(length);
+        self.complete = true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{DoubleMetaphone, Encoder};
+    use std::collections::BTreeSet;
+
+    use crate::{
+        DoubleMetaphone, DoubleMetaphoneBuilder, DoubleMetaphoneStream, Encoder, InitialVowel,
+    };
 
     /**
      * Test data from http://aspell.net/test/orig/batch0.tab.
@@ -1050,7 +1906,7 @@ mod tests {
      *
      * Massaged the test data in the array below.
      */
-    const FIXTURE: [(&str, &str); 547] = [
+    static FIXTURE: [(&str, &str); 547] = [
         ("Accosinly", "Occasionally"),
         ("Ciculer", "Circler"),
         ("Circue", "Circle"),
@@ -2188,6 +3044,282 @@ mod tests {
         double_metaphone_not_equal_test(false);
     }
 
+    #[test]
+    fn test_borrowed_codes() {
+        let double_metaphone = DoubleMetaphone::default();
+        for (value, _, _) in TEST_DATA.iter() {
+            let result = double_metaphone.double_metaphone(value);
+
+            assert_eq!(result.primary_str(), result.primary());
+            assert_eq!(result.alternate_str(), result.alternate());
+            // Borrowed codes are the internal strings, not copies.
+            assert_eq!(result.primary_str().as_ptr(), result.primary.as_ptr());
+            assert_eq!(result.alternate_str().as_ptr(), result.alternate.as_ptr());
+        }
+    }
+
+    #[test]
+    fn test_silent_letters() {
+        let double_metaphone = DoubleMetaphone::default();
+        let silent_h = DoubleMetaphone::default().with_silent_letters(&BTreeSet::from(['H']));
+        assert_eq!(
+            silent_h,
+            DoubleMetaphone::default().with_silent_letters(&BTreeSet::from(['h']))
+        );
+        assert_eq!(
+            double_metaphone.with_silent_letters(&BTreeSet::new()),
+            double_metaphone
+        );
+        // Only ASCII letters can be silent
+        assert_eq!(
+            double_metaphone.with_silent_letters(&BTreeSet::from(['É', '-'])),
+            double_metaphone
+        );
+
+        // `H` is already silent in `John`, so it's the same as `Jon`
+        assert_eq!(
+            double_metaphone.encode_pair("John"),
+            ("JN".to_string(), "AN".to_string())
+        );
+        assert_eq!(
+            silent_h.encode_pair("John"),
+            double_metaphone.encode_pair("John")
+        );
+        assert_eq!(
+            silent_h.encode_pair("John"),
+            double_metaphone.encode_pair("Jon")
+        );
+
+        assert_eq!(double_metaphone.encode("Shaw"), "X");
+        assert_eq!(silent_h.encode("Shaw"), "S");
+        assert_eq!(double_metaphone.encode("Thomas"), "TMS");
+        assert_eq!(silent_h.encode("Thomas"), "TMS");
+        assert_eq!(double_metaphone.encode("Philip"), "FLP");
+        assert_eq!(silent_h.encode("Philip"), "PLP");
+        assert_eq!(silent_h.encode("H"), "");
+        assert_eq!(silent_h.encode(" h Shaw h "), "S");
+
+        let silent_h = silent_h.respect_internal_spaces(true);
+        assert_eq!(silent_h.encode("Philip Shaw"), "PLP S");
+
+        let annotated = DoubleMetaphone::new(None).with_silent_letters(&BTreeSet::from(['H']));
+        assert_eq!(
+            annotated.encode_annotated(" Philip"),
+            vec![(1, 'P'), (4, 'L'), (6, 'P')]
+        );
+        for (value, _, _) in TEST_DATA.iter() {
+            let code: String = annotated
+                .encode_annotated(value)
+                .iter()
+                .map(|(_, ch)| ch)
+                .collect();
+            assert_eq!(code, annotated.encode(value), "Wrong code for {value}");
+
+            let mut stream = DoubleMetaphoneStream::new(silent_h.respect_internal_spaces(false));
+            stream.push(value);
+            assert_eq!(
+                stream.finish(),
+                silent_h
+                    .respect_internal_spaces(false)
+                    .double_metaphone(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_initial_vowel() {
+        let data = [
+            (InitialVowel::AlwaysA, ["AT", "AT", "AL"]),
+            (InitialVowel::Preserve, ["OT", "AT", "EL"]),
+            (InitialVowel::Drop, ["T", "T", "L"]),
+        ];
+        for (initial_vowel, expected) in data {
+            let encoder = DoubleMetaphone::default().initial_vowel(initial_vowel);
+            for (value, expected) in ["Otto", "Auto", "Ewell"].iter().zip(expected) {
+                assert_eq!(
+                    encoder.encode(value),
+                    expected,
+                    "{value} with {initial_vowel:?}"
+                );
+                assert_eq!(
+                    encoder.encode_alternate(value),
+                    expected,
+                    "{value} with {initial_vowel:?}"
+                );
+            }
+        }
+
+        assert_eq!(
+            DoubleMetaphone::default().initial_vowel,
+            InitialVowel::AlwaysA
+        );
+        // Only the first vowel is concerned
+        let encoder = DoubleMetaphone::default().initial_vowel(InitialVowel::Preserve);
+        assert_eq!(encoder.encode("Smith"), "SM0");
+        assert_eq!(encoder.encode("Ulrich"), "ULRX");
+    }
+
+    #[test]
+    fn test_concat() {
+        let encoder = DoubleMetaphone::default();
+        let john = encoder.double_metaphone("John");
+        let smith = encoder.double_metaphone("Smith");
+
+        let result = john.concat(&smith, ' ');
+        assert_eq!(result.primary(), "JN SM0");
+        assert_eq!(result.alternate(), "AN XMT");
+
+        let result = smith.concat(&john, '-');
+        assert_eq!(result.primary(), "SM0-JN");
+        assert_eq!(result.alternate(), "XMT-AN");
+
+        let empty = encoder.double_metaphone("");
+        assert_eq!(john.concat(&empty, ' ').primary(), "JN");
+        assert_eq!(empty.concat(&john, ' ').alternate(), "AN");
+    }
+
+    #[test]
+    fn test_double_metaphone_all() {
+        let double_metaphone = DoubleMetaphone::default();
+        let values = ["jumped", "Smith", "Xavier"];
+
+        let results = double_metaphone.double_metaphone_all(&values);
+        assert_eq!(results.len(), values.len());
+        for (value, result) in values.iter().zip(results.iter()) {
+            assert_eq!(result.primary_str(), double_metaphone.encode(value));
+            assert_eq!(
+                result.alternate_str(),
+                double_metaphone.encode_alternate(value)
+            );
+        }
+
+        assert!(double_metaphone.double_metaphone_all(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_encode_both() {
+        for max_code_length in [Some(4), None] {
+            let double_metaphone = DoubleMetaphone::new(max_code_length);
+            for (value, _, _) in TEST_DATA.iter() {
+                let expected = (
+                    double_metaphone.encode(value),
+                    double_metaphone.encode_alternate(value),
+                );
+
+                let result = double_metaphone.encode_both(value);
+                assert_eq!(result.primary(), expected.0, "Wrong primary for {value}");
+                assert_eq!(
+                    result.alternate(),
+                    expected.1,
+                    "Wrong alternate for {value}"
+                );
+                assert_eq!(result.into_codes(), expected, "Wrong codes for {value}");
+                assert_eq!(
+                    double_metaphone.encode_pair(value),
+                    expected,
+                    "Wrong pair for {value}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_annotated() {
+        let double_metaphone = DoubleMetaphone::new(None);
+        for (value, _, _) in TEST_DATA.iter() {
+            let code: String = double_metaphone
+                .encode_annotated(value)
+                .iter()
+                .map(|(_, ch)| ch)
+                .collect();
+            assert_eq!(
+                code,
+                double_metaphone.encode(value),
+                "Wrong code for {value}"
+            );
+        }
+
+        let double_metaphone = DoubleMetaphone::default();
+        assert_eq!(
+            double_metaphone.encode_annotated("  Xavier"),
+            vec![(2, 'S'), (4, 'F')]
+        );
+        assert_eq!(
+            double_metaphone.encode_annotated("Thompson"),
+            vec![(0, 'T'), (3, 'M'), (4, 'P'), (5, 'S')]
+        );
+        assert!(double_metaphone.encode_annotated("  ").is_empty());
+
+        let double_metaphone = DoubleMetaphone::default().respect_internal_spaces(true);
+        assert_eq!(
+            double_metaphone.encode_annotated("mac  caffrey"),
+            vec![(0, 'M'), (2, 'K'), (4, ' '), (5, 'K'), (7, 'F'), (9, 'R')]
+        );
+
+        let double_metaphone = DoubleMetaphoneBuilder::default().trim(false).build();
+        for value in ["  Xavier", "Thompson  ", " GNAW", "  "] {
+            let code: String = double_metaphone
+                .encode_annotated(value)
+                .iter()
+                .map(|(_, ch)| ch)
+                .collect();
+            assert_eq!(
+                code,
+                double_metaphone.encode(value),
+                "Wrong code for {value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_equality_matrix() {
+        let double_metaphone = DoubleMetaphone::default();
+        let names = [
+            "Smith",
+            "Catherine",
+            "Schmidt",
+            "Jones",
+            "Katherine",
+            "Smyth",
+        ];
+
+        let matrix = double_metaphone.equality_matrix(&names);
+
+        assert_eq!(matrix.len(), names.len());
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row.len(), names.len());
+            assert!(row[i]);
+            for (j, equal) in row.iter().enumerate() {
+                assert_eq!(*equal, matrix[j][i], "Not symmetric for {i} and {j}");
+                assert_eq!(
+                    *equal,
+                    double_metaphone.matches_codes(
+                        names[i],
+                        &double_metaphone.double_metaphone(names[j]).primary(),
+                        &double_metaphone.double_metaphone(names[j]).alternate()
+                    )
+                );
+            }
+        }
+
+        // Clusters
+        assert_eq!(matrix[0], vec![true, false, true, false, false, true]);
+        assert_eq!(matrix[1], vec![false, true, false, false, true, false]);
+        assert_eq!(matrix[3], vec![false, false, false, true, false, false]);
+
+        assert!(double_metaphone.equality_matrix(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_matches_codes() {
+        let double_metaphone = DoubleMetaphone::default();
+        let stored = double_metaphone.double_metaphone("Smith");
+
+        assert!(double_metaphone.matches_codes("Schmidt", &stored.primary(), &stored.alternate()));
+        assert!(double_metaphone.matches_codes("Smith", &stored.primary(), &stored.alternate()));
+        assert!(!double_metaphone.matches_codes("Jones", &stored.primary(), &stored.alternate()));
+    }
+
     #[test]
     fn test_n_tilde() {
         let encoder = DoubleMetaphone::default();
@@ -2228,7 +3360,7 @@ mod tests {
         );
     }
 
-    const TEST_DATA: [(&str, &str, &str); 1221] = [
+    static TEST_DATA: [(&str, &str, &str); 1221] = [
         ("ALLERTON", "ALRT", "ALRT"),
         ("Acton", "AKTN", "AKTN"),
         ("Adams", "ATMS", "ATMS"),
@@ -3468,6 +4600,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_code_simhash() {
+        let double_metaphone = DoubleMetaphone::default();
+        assert_eq!(
+            double_metaphone.code_simhash("Catherine"),
+            double_metaphone.code_simhash("Katherine")
+        );
+        assert_eq!(double_metaphone.code_simhash(""), 0);
+
+        let double_metaphone = DoubleMetaphone::new(None);
+        let catherine = double_metaphone.code_simhash("Catherine");
+        let katherine = double_metaphone.code_simhash("Katherine");
+        let catherina = double_metaphone.code_simhash("Catherina");
+        let johnson = double_metaphone.code_simhash("Johnson");
+        assert!((catherine ^ katherine).count_ones() <= 3);
+        assert!((catherine ^ catherina).count_ones() < (catherine ^ johnson).count_ones());
+    }
+
+    #[test]
+    fn test_render_ipa() {
+        let double_metaphone = DoubleMetaphone::default();
+
+        assert_eq!(double_metaphone.render_ipa("the"), "θ");
+        assert_eq!(double_metaphone.render_ipa("ship"), "ʃP");
+        assert_eq!(double_metaphone.render_ipa("Schmidt"), "ʃMT");
+        assert_eq!(double_metaphone.render_ipa("jumped"), "JMPT");
+        assert_eq!(double_metaphone.render_ipa(""), "");
+    }
+
+    #[test]
+    fn test_code_ngrams() {
+        let double_metaphone = DoubleMetaphone::default();
+        assert_eq!(double_metaphone.encode("jumped"), "JMPT");
+
+        let bigrams: Vec<String> = double_metaphone
+            .code_ngrams("jumped", 2)
+            .into_iter()
+            .collect();
+        assert_eq!(bigrams, vec!["$J", "JM", "MP", "PT", "T$"]);
+
+        let trigrams: Vec<String> = double_metaphone
+            .code_ngrams("jumped", 3)
+            .into_iter()
+            .collect();
+        assert_eq!(trigrams, vec!["$$J", "$JM", "JMP", "MPT", "PT$", "T$$"]);
+
+        let unigrams: Vec<String> = double_metaphone
+            .code_ngrams("Mama", 1)
+            .into_iter()
+            .collect();
+        assert_eq!(unigrams, vec!["M"]);
+
+        assert!(double_metaphone.code_ngrams("jumped", 0).is_empty());
+        assert!(double_metaphone.code_ngrams("", 2).is_empty());
+    }
+
+    #[test]
+    fn test_builder() {
+        let expected = DoubleMetaphone::default();
+        let double_metaphone = DoubleMetaphoneBuilder::default().build();
+        assert_eq!(double_metaphone, expected);
+
+        let raw = DoubleMetaphoneBuilder::default()
+            .trim(false)
+            .uppercase(false)
+            .build();
+        for (value1, value2) in FIXTURE {
+            for value in [value1, value2] {
+                let upper = value.to_uppercase();
+                assert_eq!(
+                    double_metaphone.double_metaphone(value),
+                    expected.double_metaphone(value)
+                );
+                assert_eq!(
+                    raw.double_metaphone(&upper),
+                    expected.double_metaphone(value),
+                    "{value}"
+                );
+            }
+        }
+
+        let double_metaphone = DoubleMetaphoneBuilder::default()
+            .max_code_length(None)
+            .build();
+        assert_eq!(double_metaphone.encode("  Jumped "), "JMPT");
+        assert_eq!(double_metaphone.encode("Accomodation"), "AKMTXN");
+        assert_eq!(raw.encode(" "), "");
+        assert_eq!(raw.encode(""), "");
+    }
+
+    #[test]
+    fn test_respect_internal_spaces() {
+        let double_metaphone = DoubleMetaphone::default();
+        assert_eq!(double_metaphone.encode("mac caffrey"), "MKFR");
+        assert_eq!(double_metaphone.encode_alternate("mac caffrey"), "MKFR");
+
+        let double_metaphone = double_metaphone.respect_internal_spaces(true);
+        assert_eq!(double_metaphone.encode("mac caffrey"), "MK KFR");
+        assert_eq!(double_metaphone.encode_alternate("mac caffrey"), "MK KFR");
+        assert_eq!(double_metaphone.encode("  mac   caffrey "), "MK KFR");
+        assert_eq!(double_metaphone.encode("jumped"), "JMPT");
+        assert_eq!(double_metaphone.encode(" "), "");
+    }
+
+    #[test]
+    fn test_stream() {
+        let double_metaphone = DoubleMetaphone::default();
+
+        let mut stream = DoubleMetaphoneStream::new(double_metaphone);
+        for chunk in ["ju", "mp", "ed"] {
+            stream.push(chunk);
+        }
+        assert_eq!(stream.finish(), double_metaphone.double_metaphone("jumped"));
+    }
+
+    #[test]
+    fn test_stream_chunks() {
+        for max_code_length in [Some(4), Some(1), None] {
+            let double_metaphone = DoubleMetaphone::new(max_code_length);
+            for (value, _, _) in TEST_DATA.iter() {
+                // Repeat value to have long inputs, and add a slavo-germanic
+                // ending to check that it is taken into account.
+                for value in [
+                    value.to_string(),
+                    format!(" {value} {value} {value} "),
+                    format!("{value}{value}{value}witz"),
+                ] {
+                    let expected = double_metaphone.double_metaphone(&value);
+                    let chars: Vec<char> = value.chars().collect();
+                    for size in [1, 2, 3, 7] {
+                        let mut stream = DoubleMetaphoneStream::new(double_metaphone);
+                        for chunk in chars.chunks(size) {
+                            stream.push(&chunk.iter().collect::<String>());
+                        }
+                        assert_eq!(
+                            stream.finish(),
+                            expected,
+                            "Wrong result for {value} with chunk size {size}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_no_trim() {
+        let double_metaphone = DoubleMetaphoneBuilder::default().trim(false).build();
+        for (value, _, _) in TEST_DATA.iter() {
+            for value in [format!("  {value}"), format!("{value}{value}{value} ")] {
+                let expected = double_metaphone.double_metaphone(&value);
+                let chars: Vec<char> = value.chars().collect();
+                for size in [1, 3] {
+                    let mut stream = DoubleMetaphoneStream::new(double_metaphone);
+                    for chunk in chars.chunks(size) {
+                        stream.push(&chunk.iter().collect::<String>());
+                    }
+                    assert_eq!(
+                        stream.finish(),
+                        expected,
+                        "Wrong result for {value:?} with chunk size {size}"
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_overflow_contains_case_1() {
         let encoder = DoubleMetaphone::default();
@@ -3486,6 +4785,39 @@ mod tests {
         assert_eq!(result, "XL");
     }
 
+    #[test]
+    fn test_overflow_condition_l0() {
+        let encoder = DoubleMetaphone::default();
+
+        assert_eq!(encoder.encode("LL"), "L");
+        assert_eq!(encoder.encode_alternate(" LL "), "L");
+    }
+
+    /// Encode every string of `length` characters from `alphabet`, it must not panic.
+    fn encode_all(alphabet: &[char], length: u32) {
+        let encoder = DoubleMetaphone::default();
+        let mut value = String::with_capacity(length as usize);
+        for mut n in 0..alphabet.len().pow(length) {
+            value.clear();
+            for _ in 0..length {
+                value.push(alphabet[n % alphabet.len()]);
+                n /= alphabet.len();
+            }
+            encoder.double_metaphone(&value);
+        }
+    }
+
+    #[test]
+    fn test_no_overflow_short_strings() {
+        let printable = (' '..='~').collect::<Vec<char>>();
+        for length in 1..=3 {
+            encode_all(&printable, length);
+        }
+
+        let letters = ('A'..='Z').chain([' ', '\'']).collect::<Vec<char>>();
+        encode_all(&letters, 4);
+    }
+
     #[test]
     fn test_unbounded_1() {
         let encoder = DoubleMetaphone::new(None);
@@ -0,0 +1,175 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::{end_of_line, folding, multiline_comment, Encoder};
+
+const FOLDING_RULES: &str = include_str!("../rules/folding.txt");
+
+lazy_static! {
+    static ref FOLDING: BTreeMap<char, char> = parse_folding_rules(FOLDING_RULES);
+}
+
+/// Parse ASCII folding rules, in the same format as Daitch-Mokotoff's ones (`à=a`).
+///
+/// Rules are embedded, so they are expected to be valid.
+fn parse_folding_rules(mut remains: &str) -> BTreeMap<char, char> {
+    let mut result = BTreeMap::new();
+    while !remains.is_empty() {
+        if let Ok((rm, (pattern, replacement))) = folding()(remains) {
+            result.insert(pattern, replacement);
+            remains = rm;
+        } else if let Ok((rm, _)) = end_of_line()(remains) {
+            remains = rm;
+        } else if let Ok((rm, _)) = multiline_comment()(remains) {
+            remains = rm;
+        } else {
+            panic!("Invalid ASCII folding rule : {remains}");
+        }
+    }
+
+    result
+}
+
+/// Fold a single char. Uppercase chars are folded like their lowercase
+/// counterpart and stay uppercase.
+fn fold_char(ch: char) -> char {
+    if ch.is_ascii() {
+        return ch;
+    }
+    if let Some(folded) = FOLDING.get(&ch) {
+        return *folded;
+    }
+
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => FOLDING
+            .get(&lower)
+            .map(|folded| folded.to_ascii_uppercase())
+            .unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+/// Apply ASCII folding to `value` (eg. `á` becomes `a`, `Ç` becomes `C`).
+///
+/// Chars that have no folding rule are kept as is. If `value` is already
+/// ASCII, it is returned without allocation.
+///
+/// # Parameter
+///
+/// * `value`: the value to fold.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::fold_ascii;
+///
+/// assert_eq!(fold_ascii("Łódź"), "Lodz");
+/// ```
+pub fn fold_ascii(value: &str) -> Cow<'_, str> {
+    if value.is_ascii() {
+        return Cow::Borrowed(value);
+    }
+
+    Cow::Owned(value.chars().map(fold_char).collect())
+}
+
+/// Wrapper around an [Encoder] that applies [fold_ascii] before delegating
+/// to the wrapped encoder.
+///
+/// Folding rules are the same as [DaitchMokotoffSoundex](crate::DaitchMokotoffSoundex)
+/// ones, extended to latin letters with diacritics.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Encoder, FoldAscii, Metaphone};
+///
+/// let encoder = FoldAscii::new(Metaphone::default());
+///
+/// assert_eq!(encoder.encode("François"), encoder.encode("Francois"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FoldAscii<E> {
+    encoder: E,
+}
+
+impl<E: Encoder> FoldAscii<E> {
+    /// Wrap `encoder`.
+    ///
+    /// # Parameter
+    ///
+    /// * `encoder`: the encoder to delegate to.
+    pub fn new(encoder: E) -> Self {
+        Self { encoder }
+    }
+
+    /// Return the wrapped encoder.
+    pub fn inner(&self) -> &E {
+        &self.encoder
+    }
+}
+
+impl<E: Encoder> Encoder for FoldAscii<E> {
+    fn encode(&self, s: &str) -> String {
+        self.encoder.encode(&fold_ascii(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Metaphone, Soundex};
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(FOLDING.get(&'ß'), Some(&'s'));
+        assert_eq!(FOLDING.get(&'ç'), Some(&'c'));
+        assert_eq!(FOLDING.get(&'ế'), Some(&'e'));
+        assert!(FOLDING.values().all(|ch| ch.is_ascii_lowercase()));
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold_ascii("Désmésüres"), "Desmesures".to_string());
+        assert_eq!(fold_ascii("ÇA"), "CA".to_string());
+        assert_eq!(fold_ascii("Straße"), "Strase".to_string());
+        assert_eq!(fold_ascii("Ωmega"), "Ωmega".to_string());
+        assert!(matches!(
+            fold_ascii("Desmeasures"),
+            Cow::Borrowed("Desmeasures")
+        ));
+    }
+
+    #[test]
+    fn test_metaphone() {
+        let encoder = FoldAscii::new(Metaphone::default());
+
+        assert_eq!(encoder.encode("Désmésüres"), encoder.encode("Desmeasures"));
+        assert_eq!(encoder.encode("Désmésüres"), "TSMS");
+        assert_eq!(encoder.inner(), &Metaphone::default());
+    }
+
+    #[test]
+    fn test_soundex() {
+        let encoder = FoldAscii::new(Soundex::default());
+
+        assert_eq!(encoder.encode("Çelik"), "C420");
+    }
+}
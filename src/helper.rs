@@ -14,10 +14,23 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::fmt::{Display, Formatter};
-use std::ops::{Index, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::ops::{Index, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::io::{BufRead, Write};
 
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{soundex_letters, Encoder, NonLetterPolicy};
 
 /// Replace regex like "s+" by a single char "S".
 pub fn replace_compact_all_to_uppercase(string: String, chars: Vec<char>) -> String {
@@ -74,6 +87,395 @@ pub fn remove_all_non_letter(string: String) -> String {
         .collect::<String>()
 }
 
+/// Trim `value` and collapse every run of whitespaces into a single space.
+///
+/// If `value` is already normalized, it is returned without allocation.
+///
+/// # Parameter
+///
+/// * `value`: the value to normalize.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::normalize_whitespace;
+///
+/// assert_eq!(normalize_whitespace("  van \t  Dyke "), "van Dyke");
+/// ```
+pub fn normalize_whitespace(value: &str) -> Cow<'_, str> {
+    let value = value.trim();
+
+    let mut previous_is_whitespace = false;
+    let normalized = value.chars().all(|ch| {
+        let ok = !ch.is_whitespace() || (ch == ' ' && !previous_is_whitespace);
+        previous_is_whitespace = ch.is_whitespace();
+        ok
+    });
+    if normalized {
+        return Cow::Borrowed(value);
+    }
+
+    Cow::Owned(value.split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+/// Keep only the letters of `value`, uppercased. This is the cleaning applied by
+/// soundex-like encoders (eg. [Nysiis](crate::Nysiis) or [Phonex](crate::Phonex)),
+/// it can be used to build custom blocking keys.
+///
+/// # Parameter
+///
+/// * `value`: the value to clean.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::soundex_clean;
+///
+/// assert_eq!(soundex_clean("O'Brien-Smith 3rd"), "OBRIENSMITHRD");
+/// ```
+pub fn soundex_clean(value: &str) -> String {
+    soundex_letters(value, NonLetterPolicy::Drop).collect()
+}
+
+/// Normalize `value` to Unicode NFC, so combining marks are composed with
+/// the letter they follow when possible (eg. `e` followed by `U+0301` becomes `é`).
+///
+/// Encoders work on chars, so a decomposed `é` is seen as an `e` followed
+/// by an unknown char and doesn't produce the same code as a composed `é`.
+///
+/// If `value` is already in NFC, it is returned without allocation.
+///
+/// # Parameter
+///
+/// * `value`: the value to normalize.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::normalize_nfc;
+///
+/// assert_eq!(normalize_nfc("Rene\u{0301}e"), "Ren\u{00E9}e");
+/// assert_eq!(normalize_nfc("Ren\u{00E9}e"), "Ren\u{00E9}e");
+/// ```
+#[cfg(feature = "unicode-normalization")]
+pub fn normalize_nfc(value: &str) -> Cow<'_, str> {
+    if unicode_normalization::is_nfc(value) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(value.nfc().collect())
+    }
+}
+
+/// Wrapper around an [Encoder] that applies [normalize_nfc] before
+/// delegating to the wrapped encoder. This way, composed and decomposed
+/// forms of a string produce the same code.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{DoubleMetaphone, Encoder, Normalized};
+///
+/// let encoder = Normalized::new(DoubleMetaphone::default());
+///
+/// assert_eq!(encoder.encode("Ren\u{00E9}e"), encoder.encode("Rene\u{0301}e"));
+/// ```
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Normalized<E> {
+    encoder: E,
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl<E: Encoder> Normalized<E> {
+    /// Wrap `encoder`.
+    ///
+    /// # Parameter
+    ///
+    /// * `encoder`: the encoder to delegate to.
+    pub fn new(encoder: E) -> Self {
+        Self { encoder }
+    }
+
+    /// Return the wrapped encoder.
+    pub fn inner(&self) -> &E {
+        &self.encoder
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl<E: Encoder> Encoder for Normalized<E> {
+    fn encode(&self, s: &str) -> String {
+        self.encoder.encode(&normalize_nfc(s))
+    }
+}
+
+/// Return `true` if `ch` is an apostrophe, either typewriter (`'`) or typographic (`’`).
+fn is_apostrophe(ch: char) -> bool {
+    matches!(ch, '\'' | '\u{2019}')
+}
+
+/// Split a name field into tokens. Tokens are separated by whitespaces and common
+/// name punctuation : hyphen, apostrophe, period and comma. Empty tokens are dropped.
+///
+/// See [tokenize_name_with] to keep apostrophes inside tokens.
+///
+/// # Parameter
+///
+/// * `value`: the name to split.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::tokenize_name;
+///
+/// assert_eq!(tokenize_name("O'Brien-Smith Jr."), vec!["O", "Brien", "Smith", "Jr"]);
+/// ```
+pub fn tokenize_name(value: &str) -> Vec<&str> {
+    tokenize_name_with(value, false)
+}
+
+/// Split a name field into tokens, like [tokenize_name].
+///
+/// If `keep_apostrophes` is `true`, an apostrophe between two letters does not split
+/// the token (eg. `O'Brien`). Leading and trailing apostrophes are always removed.
+///
+/// # Parameters
+///
+/// * `value`: the name to split.
+/// * `keep_apostrophes`: whether intra-token apostrophes are kept.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::tokenize_name_with;
+///
+/// assert_eq!(tokenize_name_with("O'Brien-Smith Jr.", true), vec!["O'Brien", "Smith", "Jr"]);
+/// assert_eq!(tokenize_name_with("'Jones'", true), vec!["Jones"]);
+/// ```
+pub fn tokenize_name_with(value: &str, keep_apostrophes: bool) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        let is_separator = if is_apostrophe(ch) {
+            let next = chars.peek().map(|(_, next)| *next);
+            !(keep_apostrophes
+                && previous.map(char::is_alphabetic).unwrap_or(false)
+                && next.map(char::is_alphabetic).unwrap_or(false))
+        } else {
+            ch.is_whitespace() || matches!(ch, '-' | '.' | ',')
+        };
+
+        if is_separator {
+            if start < index {
+                result.push(&value[start..index]);
+            }
+            start = index + ch.len_utf8();
+        }
+        previous = Some(ch);
+    }
+    if start < value.len() {
+        result.push(&value[start..]);
+    }
+
+    result
+}
+
+/// Encode names read from `reader`, one per line, and write their codes to `writer`,
+/// one per line. Lines are read one at a time, so the whole input is never loaded
+/// into memory.
+///
+/// Line endings (`\n` or `\r\n`) are not part of the encoded value.
+///
+/// # Parameters
+///
+/// * `encoder`: the encoder used to compute codes.
+/// * `reader`: names to encode, one per line.
+/// * `writer`: where codes are written, each followed by `\n`.
+///
+/// # Error
+///
+/// This function returns an error if it can't read from `reader` or write into `writer`.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> std::io::Result<()> {
+/// use std::io::Cursor;
+/// use rphonetic::{encode_lines, Soundex};
+///
+/// let mut output = Vec::new();
+/// encode_lines(&Soundex::default(), Cursor::new("Robert\nAshcraft\n"), &mut output)?;
+///
+/// assert_eq!(output, b"R163\nA261\n");
+/// #   Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_lines<R: BufRead, W: Write, E: Encoder + ?Sized>(
+    encoder: &E,
+    mut reader: R,
+    mut writer: W,
+) -> io::Result<()> {
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let value = line.trim_end_matches('\n').trim_end_matches('\r');
+        writer.write_all(encoder.encode(value).as_bytes())?;
+        writer.write_all(b"\n")?;
+        line.clear();
+    }
+
+    writer.flush()
+}
+
+/// Pick the representative spelling of a cluster of spellings : it is a spelling whose
+/// code is the most frequent in the cluster. If there are multiple candidates, the
+/// shortest spelling (in chars) is chosen, and then the first one.
+///
+/// # Parameters
+///
+/// * `encoder`: the encoder used to compute codes.
+/// * `spellings`: the spellings of the cluster.
+///
+/// # Return
+///
+/// The representative spelling, or [None] if `spellings` is empty.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{canonicalize_cluster, Soundex};
+///
+/// let soundex = Soundex::default();
+/// let spellings = ["Katherine", "Catherine", "Kathryn", "Cathryn", "Kathrine"];
+///
+/// // K365 is the most frequent code
+/// assert_eq!(canonicalize_cluster(&soundex, &spellings), Some("Kathryn".to_string()));
+/// ```
+pub fn canonicalize_cluster(encoder: &impl Encoder, spellings: &[&str]) -> Option<String> {
+    let codes: Vec<String> = spellings
+        .iter()
+        .map(|value| encoder.encode(value))
+        .collect();
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for code in codes.iter() {
+        *counts.entry(code).or_default() += 1;
+    }
+    let max = counts.values().max()?;
+
+    spellings
+        .iter()
+        .zip(codes.iter())
+        .filter(|(_, code)| counts.get(code.as_str()) == Some(max))
+        .min_by_key(|(spelling, _)| spelling.chars().count())
+        .map(|(spelling, _)| spelling.to_string())
+}
+
+/// Compute the [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+/// between `first` and `second`, in chars.
+pub fn levenshtein(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    let mut current: Vec<usize> = vec![0; second.len() + 1];
+
+    for (i, ch1) in first.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, ch2) in second.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ch1 != *ch2);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[second.len()]
+}
+
+/// Compute the 64 bits [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) hash of `bytes`.
+///
+/// Unlike [DefaultHasher](std::collections::hash_map::DefaultHasher), it is stable across
+/// Rust versions and platforms.
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(PRIME)
+    })
+}
+
+/// Script of a text, see [detect_script].
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Script {
+    /// Latin alphabet, with or without diacritics.
+    Latin,
+    /// Cyrillic alphabet.
+    Cyrillic,
+    /// Greek alphabet.
+    Greek,
+    /// Any other script, or no letter at all.
+    Other,
+}
+
+impl Script {
+    fn of(ch: char) -> Self {
+        match ch {
+            'a'..='z'
+            | 'A'..='Z'
+            | '\u{00AA}'
+            | '\u{00BA}'
+            | '\u{00C0}'..='\u{00D6}'
+            | '\u{00D8}'..='\u{00F6}'
+            | '\u{00F8}'..='\u{024F}'
+            | '\u{1E00}'..='\u{1EFF}' => Script::Latin,
+            '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Script::Greek,
+            _ => Script::Other,
+        }
+    }
+}
+
+/// Detect the likely script of `value`, based on character ranges.
+///
+/// Only alphabetic characters are taken into account, the script that
+/// has the most letters wins. This can be used to choose an appropriate
+/// encoder for `value`.
+///
+/// # Parameter
+///
+/// * `value`: the value to classify.
+///
+/// # Return
+///
+/// The [Script] of the majority of `value`'s letters or [Script::Other] if
+/// `value` has no letter.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{detect_script, Script};
+///
+/// assert_eq!(detect_script("M\u{00FC}ller"), Script::Latin);
+/// assert_eq!(detect_script("\u{041F}\u{0451}\u{0442}\u{0440}"), Script::Cyrillic);
+/// assert_eq!(detect_script("1234"), Script::Other);
+/// ```
+pub fn detect_script(value: &str) -> Script {
+    // Latin, Cyrillic, Greek, Other
+    let mut counts = [0usize; 4];
+    value
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .for_each(|ch| counts[Script::of(ch) as usize] += 1);
+
+    [Script::Latin, Script::Cyrillic, Script::Greek]
+        .into_iter()
+        .filter(|script| counts[*script as usize] > 0)
+        .max_by_key(|script| (counts[*script as usize], core::cmp::Reverse(*script)))
+        .filter(|script| counts[*script as usize] >= counts[Script::Other as usize])
+        .unwrap_or(Script::Other)
+}
+
 /// This struct is a wrapper around an `&str` allowing
 /// to slice by char.
 ///
@@ -87,7 +489,7 @@ pub struct CharSequence<'a> {
 }
 
 impl Display for CharSequence<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.inner)
     }
 }
@@ -200,6 +602,9 @@ impl Index<RangeToInclusive<usize>> for CharSequence<'_> {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    #[cfg(feature = "unicode-normalization")]
+    use crate::DoubleMetaphone;
+    use crate::Soundex;
 
     #[test]
     fn test_vowel() {
@@ -223,6 +628,124 @@ mod tests {
         assert!(!is_vowel(None, false));
     }
 
+    #[test]
+    fn test_detect_script_latin() {
+        assert_eq!(detect_script("Smith"), Script::Latin);
+        assert_eq!(detect_script("Fran\u{00E7}ois-\u{00C9}mile"), Script::Latin);
+        assert_eq!(detect_script("Nguy\u{1EC5}n"), Script::Latin);
+    }
+
+    #[test]
+    fn test_detect_script_cyrillic() {
+        assert_eq!(
+            detect_script("\u{0418}\u{0432}\u{0430}\u{043D}\u{043E}\u{0432}"),
+            Script::Cyrillic
+        );
+        // Mostly cyrillic
+        assert_eq!(
+            detect_script("\u{0411}\u{043E}\u{0440}\u{0438}\u{0441} B."),
+            Script::Cyrillic
+        );
+    }
+
+    #[test]
+    fn test_detect_script_greek() {
+        assert_eq!(
+            detect_script("\u{0391}\u{03B8}\u{03AE}\u{03BD}\u{03B1}"),
+            Script::Greek
+        );
+        assert_eq!(
+            detect_script("\u{1F08}\u{03B8}\u{03B7}\u{03BD}\u{1FB6}"),
+            Script::Greek
+        );
+    }
+
+    #[test]
+    fn test_detect_script_other() {
+        assert_eq!(detect_script(""), Script::Other);
+        assert_eq!(detect_script(" 12-34 "), Script::Other);
+        assert_eq!(detect_script("\u{65E5}\u{672C}"), Script::Other);
+        assert_eq!(detect_script("\u{65E5}\u{672C}a"), Script::Other);
+        // Ties are resolved in declaration order
+        assert_eq!(detect_script("a\u{0430}"), Script::Latin);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_encode_lines() -> io::Result<()> {
+        let soundex = Soundex::default();
+        let input = io::Cursor::new("Robert\nRupert\r\nAshcraft");
+
+        let mut output = Vec::new();
+        encode_lines(&soundex, input, &mut output)?;
+        assert_eq!(output, b"R163\nR163\nA261\n");
+
+        let mut output = Vec::new();
+        encode_lines(&soundex, io::Cursor::new(""), &mut output)?;
+        assert!(output.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize_cluster() {
+        let soundex = Soundex::default();
+
+        let spellings = ["Katherine", "Catherine", "Kathryn", "Cathryn", "Kathrine"];
+        assert_eq!(
+            canonicalize_cluster(&soundex, &spellings),
+            Some("Kathryn".to_string())
+        );
+
+        // Same code, shortest spelling wins
+        let spellings = ["Smithe", "Schmidt", "Smyth", "Schmit"];
+        assert_eq!(
+            canonicalize_cluster(&soundex, &spellings),
+            Some("Smyth".to_string())
+        );
+
+        // Codes tie
+        let spellings = ["Robert", "Anne", "Rupert", "Ann"];
+        assert_eq!(
+            canonicalize_cluster(&soundex, &spellings),
+            Some("Ann".to_string())
+        );
+
+        // Same length, first one wins
+        let spellings = ["Jon", "Jan", "Bob"];
+        assert_eq!(
+            canonicalize_cluster(&soundex, &spellings),
+            Some("Jon".to_string())
+        );
+
+        let nysiis = crate::Nysiis::default();
+        let spellings = ["Brian", "Brown", "Brun", "Bryan", "Bruno"];
+        assert_eq!(
+            canonicalize_cluster(&nysiis, &spellings),
+            Some("Brun".to_string())
+        );
+
+        assert_eq!(canonicalize_cluster(&soundex, &[]), None);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("R163", "R163"), 0);
+        assert_eq!(levenshtein("éa", "ea"), 1);
+    }
+
+    #[test]
+    fn test_fnv1a_64() {
+        assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+    }
+
     #[test]
     fn test_replace_compact_all_to_uppercase_nothing_to_compact() {
         let result =
@@ -246,6 +769,114 @@ mod tests {
         assert_eq!(result, "aaaaBccccD");
     }
 
+    #[test]
+    fn test_normalize_whitespace() {
+        assert_eq!(normalize_whitespace("  van   Dyke "), "van Dyke");
+        assert_eq!(normalize_whitespace("van\t\nDyke"), "van Dyke");
+        assert_eq!(normalize_whitespace("van \tDyke"), "van Dyke");
+        assert_eq!(normalize_whitespace(" \t "), "");
+        assert_eq!(normalize_whitespace(""), "");
+    }
+
+    #[test]
+    fn test_soundex_clean() {
+        assert_eq!(soundex_clean("McDonald"), "MCDONALD");
+        assert_eq!(soundex_clean("o'Brien-Smith, 3rd."), "OBRIENSMITHRD");
+        assert_eq!(soundex_clean("R2-D2 & c3po!"), "RDCPO");
+        assert_eq!(soundex_clean("Müller"), "MÜLLER");
+        assert_eq!(soundex_clean("Straße"), "STRASSE");
+        assert_eq!(soundex_clean(" 42 -- "), "");
+        assert_eq!(soundex_clean(""), "");
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_normalize_nfc() {
+        assert_eq!(normalize_nfc("Rene\u{0301}e"), "Ren\u{00E9}e");
+        assert_eq!(normalize_nfc("Mu\u{0308}ller"), "M\u{00FC}ller");
+        // Several marks
+        assert_eq!(normalize_nfc("e\u{0302}\u{0301}"), "\u{1EBF}");
+        // Marks are reordered before composition
+        assert_eq!(normalize_nfc("e\u{0302}\u{0323}"), "\u{1EC7}");
+        assert_eq!(normalize_nfc("e\u{0323}\u{0302}"), "\u{1EC7}");
+        // Other scripts
+        assert_eq!(normalize_nfc("\u{30AB}\u{3099}"), "\u{30AC}");
+        // No composition
+        assert_eq!(normalize_nfc("x\u{0301}"), "x\u{0301}");
+        assert_eq!(normalize_nfc("\u{0301}e"), "\u{0301}e");
+        assert!(matches!(normalize_nfc("Renée"), Cow::Borrowed("Renée")));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_normalized_encoder() {
+        let composed = "Ren\u{00E9}e";
+        let decomposed = "Rene\u{0301}e";
+
+        let soundex = Normalized::new(Soundex::default());
+        assert_eq!(soundex.encode(composed), soundex.encode(decomposed));
+        assert_eq!(soundex.encode(decomposed), "R500");
+
+        let double_metaphone = Normalized::new(DoubleMetaphone::default());
+        assert_eq!(
+            double_metaphone.encode(composed),
+            double_metaphone.encode(decomposed)
+        );
+        assert_eq!(double_metaphone.inner(), &DoubleMetaphone::default());
+    }
+
+    #[test]
+    fn test_tokenize_name() {
+        assert_eq!(
+            tokenize_name("O'Brien-Smith Jr."),
+            vec!["O", "Brien", "Smith", "Jr"]
+        );
+        assert_eq!(
+            tokenize_name("  Smith,  John\tPaul "),
+            vec!["Smith", "John", "Paul"]
+        );
+        assert_eq!(
+            tokenize_name("Müller-Lüdenscheidt"),
+            vec!["Müller", "Lüdenscheidt"]
+        );
+        assert!(tokenize_name(" - . ' ").is_empty());
+        assert!(tokenize_name("").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_name_keep_apostrophes() {
+        assert_eq!(
+            tokenize_name_with("O'Brien-Smith Jr.", true),
+            vec!["O'Brien", "Smith", "Jr"]
+        );
+        assert_eq!(
+            tokenize_name_with("d\u{2019}Artagnan", true),
+            vec!["d\u{2019}Artagnan"]
+        );
+        assert_eq!(
+            tokenize_name_with("'Jones' Rock 'n' Roll", true),
+            vec!["Jones", "Rock", "n", "Roll"]
+        );
+        assert_eq!(tokenize_name_with("O''Brien", true), vec!["O", "Brien"]);
+        assert_eq!(
+            tokenize_name_with("O'Brien", false),
+            tokenize_name("O'Brien")
+        );
+    }
+
+    #[test]
+    fn test_normalize_whitespace_borrowed() {
+        assert!(matches!(
+            normalize_whitespace("van Dyke"),
+            Cow::Borrowed("van Dyke")
+        ));
+        assert!(matches!(
+            normalize_whitespace("  van Dyke "),
+            Cow::Borrowed("van Dyke")
+        ));
+        assert!(matches!(normalize_whitespace("van  Dyke"), Cow::Owned(_)));
+    }
+
     #[test]
     fn test_char_sequence_all_char_range() {
         for ch in '\u{0000}'..'\u{ffff}' {
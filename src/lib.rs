@@ -21,6 +21,7 @@
 //!
 //! * [Caverphone1] : see [Wikipedia](https://en.wikipedia.org/wiki/Caverphone).
 //! * [Caverphone2] : see [Wikipedia](https://en.wikipedia.org/wiki/Caverphone).
+//! * [Caverphone] : [Caverphone1] or [Caverphone2], the revision being selected at runtime.
 //! * [Cologne] : see [Wikipedia](https://en.wikipedia.org/wiki/Cologne_phonetics).
 //! * [DaitchMokotoffSoundex] : see [Wikipedia](https://en.wikipedia.org/wiki/Daitch%E2%80%93Mokotoff_Soundex)
 //! * [DoubleMetaphone] : see [Wikipedia](https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone)
@@ -31,6 +32,7 @@
 //! * [Soundex] : see [Wikipedia](https://en.wikipedia.org/wiki/Soundex)
 //! * [BeiderMorse] : see [Wikipedia](https://en.wikipedia.org/wiki/Daitch%E2%80%93Mokotoff_Soundex#Beider%E2%80%93Morse_Phonetic_Name_Matching_Algorithm)
 //! * [Phonex] see [paper](https://citeseerx.ist.psu.edu/viewdoc/download;jsessionid=E3997DC51F2046A95EE6459F2B997029?doi=10.1.1.453.4046&rep=rep1&type=pdf)
+//! * [Phonix] see [paper](https://www.researchgate.net/publication/220308114_PHONIX_The_algorithm)
 //!
 //! Please note that most of these algorithms are design for ASCII, and they are usually design for certain use case (eg.
 //! english names, ...etc).
@@ -48,46 +50,87 @@
     unused_qualifications
 )]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate lazy_static;
 
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "smol_str")]
+use smol_str::ToSmolStr;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::fmt::{Display, Formatter};
 
+use either::Either;
+#[cfg(feature = "std")]
 use rules_parser::*;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
 pub use crate::beider_morse::{
     BMError, BeiderMorse, BeiderMorseBuilder, ConfigFiles, LanguageSet, NameType, RuleType,
 };
-pub use crate::caverphone::{Caverphone1, Caverphone2};
+pub use crate::caverphone::{Caverphone, Caverphone1, Caverphone2, CaverphoneRevision};
 pub use crate::cologne::Cologne;
+pub use crate::config::{Algorithm, AnyEncoder, EncoderConfig};
+#[cfg(feature = "std")]
 pub use crate::daitch_mokotoff::{DaitchMokotoffSoundex, DaitchMokotoffSoundexBuilder};
-pub use crate::double_metaphone::{DoubleMetaphone, DoubleMetaphoneResult};
-pub use crate::helper::CharSequence;
-pub use crate::match_rating_approach::MatchRatingApproach;
-pub use crate::metaphone::Metaphone;
-pub use crate::nysiis::Nysiis;
+pub use crate::double_metaphone::{
+    DoubleMetaphone, DoubleMetaphoneBuilder, DoubleMetaphoneResult, DoubleMetaphoneStream,
+    InitialVowel,
+};
+#[cfg(feature = "std")]
+pub use crate::fold_ascii::{fold_ascii, FoldAscii};
+#[cfg(feature = "std")]
+pub use crate::helper::encode_lines;
+pub use crate::helper::{
+    canonicalize_cluster, detect_script, normalize_whitespace, soundex_clean, tokenize_name,
+    tokenize_name_with, CharSequence, Script,
+};
+#[cfg(feature = "unicode-normalization")]
+pub use crate::helper::{normalize_nfc, Normalized};
+pub use crate::match_rating_approach::{CustomMatchRatingApproach, MatchRatingApproach};
+pub use crate::metaphone::{Metaphone, MetaphoneBuilder};
+pub use crate::nysiis::{Nysiis, NysiisBuilder};
 pub use crate::phonex::Phonex;
+pub use crate::phonix::Phonix;
 pub use crate::refined_soundex::RefinedSoundex;
 pub use crate::soundex::{
-    Soundex, DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX, DEFAULT_US_ENGLISH_MAPPING_SOUNDEX,
+    HwRule, Soundex, DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX,
+    DEFAULT_US_ENGLISH_MAPPING_SOUNDEX,
 };
+#[cfg(feature = "smol_str")]
+pub use smol_str::SmolStr;
 
+#[cfg(feature = "std")]
 mod beider_morse;
 mod caverphone;
 mod cologne;
+mod config;
+#[cfg(feature = "std")]
 mod daitch_mokotoff;
 mod double_metaphone;
+#[cfg(feature = "std")]
+mod fold_ascii;
 mod helper;
 mod match_rating_approach;
 mod metaphone;
 mod nysiis;
 mod phonex;
+mod phonix;
 mod refined_soundex;
+#[cfg(feature = "std")]
 mod rules_parser;
 mod soundex;
+#[cfg(test)]
+mod soundex_fixtures;
 
 /// This represents a parsing error. It contains the
 /// line number, the line, and if possible the filename.
@@ -118,29 +161,57 @@ impl Display for ParseError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for ParseError {}
 
 /// Errors
+///
+/// Variants depend on enabled features (eg. `BMError` requires `std`),
+/// so this enum is non-exhaustive.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum PhoneticError {
     /// This variant contains parsing errors.
     ParseRuleError(ParseError),
+    /// This variant is returned when a mapping string (eg. [Soundex::from_mapping]) is
+    /// not valid.
+    InvalidMapping {
+        /// Why the mapping is not valid.
+        reason: String,
+    },
+    /// This variant is returned when a mapping of a strict encoder (eg. [RefinedSoundex::strict])
+    /// contains non-ASCII codes, they are provided in the order of the mapping.
+    NonAsciiMapping(Vec<char>),
+    /// This variant is returned when a parameter of an encoder is not valid, eg. a
+    /// maximum length of 0 in an [EncoderConfig].
+    InvalidParameter {
+        /// Name of the parameter.
+        name: String,
+        /// Why the value is not valid.
+        reason: String,
+    },
+    /// This variant is returned when an [Algorithm] name is unknown.
+    UnknownAlgorithm(String),
     /// This error contains errors related to Beider Morse.
+    #[cfg(feature = "std")]
     BMError(BMError),
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for PhoneticError {
     fn from(error: std::io::Error) -> Self {
         Self::BMError(BMError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<regex::Error> for PhoneticError {
     fn from(error: regex::Error) -> Self {
         Self::BMError(BMError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<BMError> for PhoneticError {
     fn from(error: BMError) -> Self {
         Self::BMError(error)
@@ -151,11 +222,19 @@ impl Display for PhoneticError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::ParseRuleError(error) => write!(f, "Error parsing rule file {error}"),
+            Self::InvalidMapping { reason } => write!(f, "Invalid mapping : {reason}"),
+            Self::NonAsciiMapping(codes) => write!(f, "Non-ASCII codes in mapping : {codes:?}"),
+            Self::InvalidParameter { name, reason } => {
+                write!(f, "Invalid parameter '{name}' : {reason}")
+            }
+            Self::UnknownAlgorithm(name) => write!(f, "Unknown algorithm '{name}'"),
+            #[cfg(feature = "std")]
             Self::BMError(error) => write!(f, "Error : {error}"),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for PhoneticError {}
 
 fn build_error(
@@ -179,6 +258,9 @@ fn build_error(
     })
 }
 
+/// Padding character used by [code_ngrams](Encoder::code_ngrams).
+pub const NGRAM_PADDING: char = '$';
+
 /// This trait represents a phonetic algorithm.
 pub trait Encoder {
     /// This method convert a string into its code.
@@ -204,6 +286,183 @@ pub trait Encoder {
     /// ```
     fn encode(&self, s: &str) -> String;
 
+    /// This method convert anything that can be viewed as a string into its code.
+    /// It calls [encode(value)](Encoder::encode).
+    ///
+    /// # Parameter
+    ///
+    /// * `s` : string to encode, it could be a [String], a `&`[String], a [Cow], ...etc.
+    ///
+    /// # Return
+    ///
+    /// String encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Caverphone1, Encoder};
+    ///
+    /// let caverphone = Caverphone1;
+    ///
+    /// assert_eq!(caverphone.encode_any(String::from("Thompson")), "TMPSN1");
+    /// ```
+    fn encode_any<S: AsRef<str>>(&self, s: S) -> String
+    where
+        Self: Sized,
+    {
+        self.encode(s.as_ref())
+    }
+
+    /// This method convert a string into its code and write it into `out`.
+    /// `out` is cleared first, so its allocation can be reused across calls.
+    ///
+    /// The default implementation calls [encode(value)](Encoder::encode), some
+    /// encoders override it to avoid allocating a new [String].
+    ///
+    /// # Parameters
+    ///
+    /// * `s` : string to encode.
+    /// * `out` : buffer that will contain the code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// let mut buffer = String::new();
+    ///
+    /// soundex.encode_into("Robert", &mut buffer);
+    /// assert_eq!(buffer, "R163");
+    /// soundex.encode_into("Ashcraft", &mut buffer);
+    /// assert_eq!(buffer, "A261");
+    /// ```
+    fn encode_into(&self, s: &str, out: &mut String) {
+        out.clear();
+        out.push_str(&self.encode(s));
+    }
+
+    /// This method convert a string into its code, borrowing it when no allocation
+    /// is needed, for instance when `s` is already a code or when the code is empty.
+    ///
+    /// The default implementation calls [encode(value)](Encoder::encode) and always
+    /// returns [Cow::Owned], some encoders override it.
+    ///
+    /// # Parameter
+    ///
+    /// * `s` : string to encode.
+    ///
+    /// # Return
+    ///
+    /// String encoded, same as [encode(value)](Encoder::encode).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.encode_cow("Robert"), "R163");
+    /// assert!(matches!(soundex.encode_cow("R000"), Cow::Borrowed("R000")));
+    /// assert!(matches!(soundex.encode_cow("123"), Cow::Borrowed("")));
+    /// ```
+    fn encode_cow<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        Cow::Owned(self.encode(s))
+    }
+
+    /// This method convert a string, given as bytes, into its code, as bytes.
+    ///
+    /// By default, `s` is validated as UTF-8 and invalid sequences are replaced
+    /// by `U+FFFD` (see [from_utf8_lossy](String::from_utf8_lossy)) before calling
+    /// [encode](Encoder::encode). [Caverphone2] only handles ASCII and works directly
+    /// on bytes : non-ASCII bytes are skipped. [Soundex] works directly on ASCII bytes
+    /// and skips invalid UTF-8 sequences.
+    ///
+    /// # Parameter
+    ///
+    /// * `s` : bytes to encode.
+    ///
+    /// # Return
+    ///
+    /// The code, as bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Metaphone, Soundex};
+    ///
+    /// assert_eq!(Soundex::default().encode_bytes(b"Robert"), b"R163");
+    /// assert_eq!(Metaphone::default().encode_bytes(b"Joanne"), b"JN");
+    /// ```
+    fn encode_bytes(&self, s: &[u8]) -> Vec<u8> {
+        self.encode(&String::from_utf8_lossy(s)).into_bytes()
+    }
+
+    /// This method convert a string into its code, truncated to at most `max` characters.
+    /// Truncation is done on characters, not bytes, so the result is always a valid string.
+    ///
+    /// It is useful to store codes of any encoder into a fixed size column.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` : string to encode.
+    /// * `max` : maximum number of characters of the code.
+    ///
+    /// # Return
+    ///
+    /// String encoded, with at most `max` characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Cologne, Encoder};
+    ///
+    /// let cologne = Cologne;
+    ///
+    /// assert_eq!(cologne.encode("Wikipedia"), "3412");
+    /// assert_eq!(cologne.encode_bounded("Wikipedia", 3), "341");
+    /// ```
+    fn encode_bounded(&self, value: &str, max: usize) -> String {
+        let mut code = self.encode(value);
+        if let Some((index, _)) = code.char_indices().nth(max) {
+            code.truncate(index);
+        }
+
+        code
+    }
+
+    /// This method convert a string into its code, or [None] if the code is empty
+    /// (for instance when `value` has no letter).
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : string to encode.
+    ///
+    /// # Return
+    ///
+    /// `Some(code)`, or [None] if the code is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.encode_opt("Robert"), Some("R163".to_string()));
+    /// assert_eq!(soundex.encode_opt("123"), None);
+    /// ```
+    fn encode_opt(&self, value: &str) -> Option<String> {
+        let code = self.encode(value);
+        if code.is_empty() {
+            None
+        } else {
+            Some(code)
+        }
+    }
+
     /// This method check that two strings have the same code.
     ///
     /// # Parameters
@@ -232,15 +491,441 @@ pub trait Encoder {
 
         f == s
     }
+
+    /// This method check that two strings have at least one code in common. It is
+    /// meant for algorithms that produce several alternative codes separated by `|`,
+    /// like [BeiderMorse], where two strings match as soon as they share an alternative.
+    ///
+    /// Both codes are split on `|`, so for encoders that produce a single code, it is the same
+    /// as [is_encoded_equals](Encoder::is_encoded_equals).
+    /// [DaitchMokotoffSoundex] overrides it to compare branching codes, and [BeiderMorse]
+    /// to also split codes of multiple words.
+    ///
+    /// # Parameters
+    ///
+    /// * `first` : first string.
+    /// * `second` : second string.
+    ///
+    /// # Return
+    ///
+    /// Return `true` if both strings have a code in common, false otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert!(soundex.is_encoded_equals_any("Robert", "Rupert"));
+    /// assert!(!soundex.is_encoded_equals_any("Robert", "Peter"));
+    /// ```
+    fn is_encoded_equals_any(&self, first: &str, second: &str) -> bool {
+        let f = self.encode(first);
+        let s = self.encode(second);
+
+        let alternatives: BTreeSet<&str> = f.split('|').collect();
+        s.split('|').any(|code| alternatives.contains(code))
+    }
+
+    /// This method check that two strings have the same code. It is the same as
+    /// [is_encoded_equals](Encoder::is_encoded_equals), named to read naturally in matching code.
+    ///
+    /// # Parameters
+    ///
+    /// * `first` : first string.
+    /// * `second` : second string.
+    ///
+    /// # Return
+    ///
+    /// Return `true` if both strings have the same code, false otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert!(soundex.same_code("Robert", "Rupert"));
+    /// assert!(!soundex.same_code("Robert", "Peter"));
+    /// ```
+    fn same_code(&self, first: &str, second: &str) -> bool {
+        self.is_encoded_equals(first, second)
+    }
+
+    /// This method check that two strings have different codes. It is the opposite
+    /// of [same_code](Encoder::same_code).
+    ///
+    /// # Parameters
+    ///
+    /// * `first` : first string.
+    /// * `second` : second string.
+    ///
+    /// # Return
+    ///
+    /// Return `true` if strings have different codes, false otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, RefinedSoundex};
+    ///
+    /// let refined_soundex = RefinedSoundex::default();
+    /// assert!(refined_soundex.different_code("Robert", "Peter"));
+    /// assert!(!refined_soundex.different_code("Smithers", "Smythers"));
+    /// ```
+    fn different_code(&self, first: &str, second: &str) -> bool {
+        !self.same_code(first, second)
+    }
+
+    /// This method computes the set of character n-grams of the code of a string, allowing
+    /// to build an n-gram index for approximate code matching.
+    ///
+    /// The code is padded with `n - 1` [NGRAM_PADDING] at the start and at the end, so that
+    /// first and last characters are also part of `n` n-grams.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` : string to encode.
+    /// * `n` : size of n-grams.
+    ///
+    /// # Return
+    ///
+    /// The n-grams of the code, an empty set if the code is empty or `n` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::BTreeSet;
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::default();
+    ///
+    /// let expected: BTreeSet<String> = ["$J", "JM", "MP", "PT", "T$"].iter().map(|s| s.to_string()).collect();
+    /// assert_eq!(double_metaphone.code_ngrams("jumped", 2), expected);
+    /// ```
+    fn code_ngrams(&self, value: &str, n: usize) -> BTreeSet<String> {
+        let code = self.encode(value);
+        if code.is_empty() || n == 0 {
+            return BTreeSet::new();
+        }
+
+        let padding = core::iter::repeat(NGRAM_PADDING).take(n - 1);
+        let padded: Vec<char> = padding.clone().chain(code.chars()).chain(padding).collect();
+
+        padded.windows(n).map(|w| w.iter().collect()).collect()
+    }
+
+    /// This method computes a [SimHash](https://en.wikipedia.org/wiki/SimHash) fingerprint
+    /// of the code of a string. Phonetically similar strings will have fingerprints with a
+    /// small [Hamming distance](https://en.wikipedia.org/wiki/Hamming_distance), allowing
+    /// to bucket them.
+    ///
+    /// Features are the character bigrams of the code (a code of one character is a
+    /// feature by itself), hashed with [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+    /// so the fingerprint is stable.
+    ///
+    /// # Parameter
+    ///
+    /// * `value` : string to encode.
+    ///
+    /// # Return
+    ///
+    /// The fingerprint of the code, `0` if the code is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{DoubleMetaphone, Encoder};
+    ///
+    /// let double_metaphone = DoubleMetaphone::new(None);
+    ///
+    /// let catherine = double_metaphone.code_simhash("Catherine");
+    /// let katherine = double_metaphone.code_simhash("Katherine");
+    /// let johnson = double_metaphone.code_simhash("Johnson");
+    /// assert!((catherine ^ katherine).count_ones() < (catherine ^ johnson).count_ones());
+    /// ```
+    fn code_simhash(&self, value: &str) -> u64 {
+        let code: Vec<char> = self.encode(value).chars().collect();
+        let features: Vec<String> = if code.len() < 2 {
+            vec![code.iter().collect()]
+        } else {
+            code.windows(2).map(|w| w.iter().collect()).collect()
+        };
+
+        let mut weights = [0isize; 64];
+        for feature in features.iter().filter(|f| !f.is_empty()) {
+            let hash = helper::fnv1a_64(feature.as_bytes());
+            for (bit, weight) in weights.iter_mut().enumerate() {
+                if hash & (1 << bit) != 0 {
+                    *weight += 1;
+                } else {
+                    *weight -= 1;
+                }
+            }
+        }
+
+        weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0)
+            .fold(0, |result, (bit, _)| result | (1 << bit))
+    }
+
+    /// This method computes the [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+    /// between the codes of two strings, ie. the minimum number of single character insertions,
+    /// deletions or substitutions to change one code into the other.
+    ///
+    /// Unlike [difference](SoundexCommons::difference), it handles codes of different lengths.
+    ///
+    /// # Parameters
+    ///
+    /// * `first` : first string.
+    /// * `second` : second string.
+    ///
+    /// # Return
+    ///
+    /// The distance between codes, `0` if both strings have the same code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, RefinedSoundex};
+    ///
+    /// let refined_soundex = RefinedSoundex::default();
+    ///
+    /// // T6036 and T60306
+    /// assert_eq!(refined_soundex.code_edit_distance("Tarrant", "Tarranta"), 1);
+    /// ```
+    fn code_edit_distance(&self, first: &str, second: &str) -> usize {
+        helper::levenshtein(&self.encode(first), &self.encode(second))
+    }
+
+    /// This method encodes all strings of `items`.
+    ///
+    /// # Parameter
+    ///
+    /// * `items` : strings to encode.
+    ///
+    /// # Return
+    ///
+    /// Codes of `items`, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.encode_all(["Robert", "Rupert", "Ashcraft"]), vec!["R163", "R163", "A261"]);
+    /// ```
+    fn encode_all<'a, I: IntoIterator<Item = &'a str>>(&self, items: I) -> Vec<String>
+    where
+        Self: Sized,
+    {
+        self.encode_iter(items).collect()
+    }
+
+    /// This method returns an iterator that lazily encodes strings of `items`.
+    ///
+    /// # Parameter
+    ///
+    /// * `items` : strings to encode.
+    ///
+    /// # Return
+    ///
+    /// An [EncodeIter] that yields codes of `items`, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// let mut codes = soundex.encode_iter(["Robert", "Rupert"]);
+    /// assert_eq!(codes.next(), Some("R163".to_string()));
+    /// assert_eq!(codes.next(), Some("R163".to_string()));
+    /// assert_eq!(codes.next(), None);
+    /// ```
+    fn encode_iter<'a, I: IntoIterator<Item = &'a str>>(
+        &self,
+        items: I,
+    ) -> EncodeIter<'_, Self, I::IntoIter>
+    where
+        Self: Sized,
+    {
+        EncodeIter {
+            encoder: self,
+            items: items.into_iter(),
+        }
+    }
+
+    /// This method encodes all strings of `items` in parallel, using [rayon](https://docs.rs/rayon)
+    /// global thread pool.
+    ///
+    /// The encoder is shared between threads, so it must be [Sync]. Encoders that borrow
+    /// their rules, like [BeiderMorse] or [DaitchMokotoffSoundex], only need their rules to
+    /// outlive the call.
+    ///
+    /// # Parameter
+    ///
+    /// * `items` : strings to encode.
+    ///
+    /// # Return
+    ///
+    /// Codes of `items`, in the same order, same as [encode_all](Encoder::encode_all).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.par_encode_all(&["Robert", "Ashcraft"]), vec!["R163", "A261"]);
+    /// ```
+    #[cfg(feature = "parallel")]
+    fn par_encode_all(&self, items: &[&str]) -> Vec<String>
+    where
+        Self: Sized + Sync,
+    {
+        use rayon::prelude::*;
+
+        items.par_iter().map(|item| self.encode(item)).collect()
+    }
+}
+
+/// This is an iterator that lazily encodes strings with an [Encoder].
+///
+/// It is returned by [encode_iter](Encoder::encode_iter).
+#[derive(Debug, Clone)]
+pub struct EncodeIter<'e, E, I> {
+    encoder: &'e E,
+    items: I,
+}
+
+impl<'a, E: Encoder, I: Iterator<Item = &'a str>> Iterator for EncodeIter<'_, E, I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|item| self.encoder.encode(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<'a, E: Encoder, I: DoubleEndedIterator<Item = &'a str>> DoubleEndedIterator
+    for EncodeIter<'_, E, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|item| self.encoder.encode(item))
+    }
+}
+
+impl<'a, E: Encoder, I: ExactSizeIterator<Item = &'a str>> ExactSizeIterator
+    for EncodeIter<'_, E, I>
+{
+}
+
+/// Character that replaces a run of non-letters when using [NonLetterPolicy::Separator].
+const SEPARATOR: char = ' ';
+
+/// This enum tells soundex encoders how to handle non-letters (punctuation,
+/// digits, whitespaces, ...etc).
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Encoder, NonLetterPolicy, Soundex};
+///
+/// let soundex = Soundex::default();
+/// assert_eq!(soundex.encode("Ash-Croft"), "A261");
+///
+/// let soundex = Soundex::default().non_letter_policy(NonLetterPolicy::Separator);
+/// assert_eq!(soundex.encode("Ash-Croft"), "A226");
+/// ```
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum NonLetterPolicy {
+    /// Non-letters are removed (this is the default).
+    #[default]
+    Drop,
+    /// Non-letters break consonant runs, as a vowel would.
+    Separator,
+}
+
+/// Iterate over the uppercased letters of `value`, this is [soundex_clean]
+/// with [NonLetterPolicy::Drop]. With [NonLetterPolicy::Separator], each run of non-letters
+/// between two letters is replaced by a single [SEPARATOR].
+fn soundex_letters(value: &str, policy: NonLetterPolicy) -> impl Iterator<Item = char> + '_ {
+    let mut separate = false;
+    let mut started = false;
+    value.chars().flat_map(move |ch| {
+        if ch.is_alphabetic() {
+            let separator =
+                (policy == NonLetterPolicy::Separator && separate && started).then_some(SEPARATOR);
+            separate = false;
+            started = true;
+            // Both branches must return the same iterator type
+            Either::Left(separator.into_iter().chain(ch.to_uppercase()))
+        } else {
+            separate = true;
+            Either::Right(core::iter::empty())
+        }
+    })
+}
+
+/// Write the chars of a code into a [Formatter].
+#[cfg(feature = "smol_str")]
+struct FormatterOutput<'a, 'b> {
+    formatter: &'a mut Formatter<'b>,
+    result: fmt::Result,
+}
+
+#[cfg(feature = "smol_str")]
+impl Extend<char> for FormatterOutput<'_, '_> {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        for ch in iter {
+            if self.result.is_ok() {
+                self.result = fmt::Write::write_char(self.formatter, ch);
+            }
+        }
+    }
+}
+
+/// Build a [SmolStr] from the chars written by `encode`, without heap allocation
+/// if the code is short enough to be stored inline.
+#[cfg(feature = "smol_str")]
+fn encode_smol_str<F>(encode: F) -> SmolStr
+where
+    F: Fn(&mut FormatterOutput<'_, '_>),
+{
+    struct Code<F>(F);
+
+    impl<F> Display for Code<F>
+    where
+        F: Fn(&mut FormatterOutput<'_, '_>),
+    {
+        fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+            let mut output = FormatterOutput {
+                formatter,
+                result: Ok(()),
+            };
+            (self.0)(&mut output);
+            output.result
+        }
+    }
+
+    Code(encode).to_smolstr()
 }
 
 trait SoundexUtils {
     fn soundex_clean(value: &str) -> String {
-        value
-            .chars()
-            .filter(|c| c.is_alphabetic())
-            .map(|c| c.to_uppercase().collect::<String>())
-            .collect()
+        soundex_clean(value)
     }
 }
 
@@ -312,3 +997,83 @@ pub trait SoundexCommons: Encoder {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_code() {
+        let encoder = Soundex::default();
+
+        for (first, second) in [
+            ("Robert", "Rupert"),
+            ("Robert", "Peter"),
+            ("Smithers", "Smythers"),
+            ("", "Peter"),
+            ("", ""),
+        ] {
+            assert_eq!(
+                encoder.same_code(first, second),
+                encoder.is_encoded_equals(first, second)
+            );
+            assert_ne!(
+                encoder.same_code(first, second),
+                encoder.different_code(first, second),
+            );
+        }
+    }
+}
+
+/// These tests only run without the `std` feature, to check that algorithms
+/// that only need `alloc` are still available.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use enum_iterator::all;
+
+    use super::*;
+
+    #[test]
+    fn test_algorithms() {
+        let codes: Vec<String> = all::<Algorithm>()
+            .map(|algorithm| algorithm.encoder().encode("Robert"))
+            .collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                "RPT111",
+                "RPT1111111",
+                "7172",
+                "RPRT",
+                "RBRT",
+                "RBRT",
+                "RABAD",
+                "R130",
+                "R130",
+                "R901096",
+                "R163"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_helpers() -> Result<(), PhoneticError> {
+        let soundex = Soundex::from_mapping("01230120022455012623010202")?;
+
+        assert_eq!(soundex.encode("Robert"), "R163");
+        #[cfg(feature = "unicode-normalization")]
+        {
+            assert_eq!(normalize_nfc("Rene\u{0301}e"), "Ren\u{00E9}e");
+            assert_eq!(
+                Normalized::new(DoubleMetaphone::default()).encode("Rene\u{0301}e"),
+                "RN"
+            );
+        }
+
+        Ok(())
+    }
+}
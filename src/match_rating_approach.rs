@@ -14,10 +14,14 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
 use serde::{Deserialize, Serialize};
 
 use crate::helper::is_vowel;
-use crate::Encoder;
+use crate::{Encoder, PhoneticError};
 
 /// The plain letter equivalent of the accented letters.
 const PLAIN_ASCII: [char; 60] = [
@@ -65,6 +69,8 @@ const DOUBLE_CONSONANT: [(&str, &str); 21] = [
 
 const CHAR_TO_TRIM: [char; 5] = ['-', '&', '\'', '.', ','];
 
+const DEFAULT_CODEX_KEEP: usize = 3;
+
 /// This the [match rating approach](https://en.wikipedia.org/wiki/Match_rating_approach) [Encoder].
 ///
 /// # Example
@@ -79,10 +85,189 @@ const CHAR_TO_TRIM: [char; 5] = ['-', '&', '\'', '.', ','];
 /// // This does not match
 /// assert!(!match_rating.is_encoded_equals("Karl", "Alessandro"));
 /// ```
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
 pub struct MatchRatingApproach;
 
 impl MatchRatingApproach {
+    /// Build a [CustomMatchRatingApproach] that keeps `codex_keep` leading and trailing
+    /// characters when the codex is longer than twice this value, instead of 3.
+    ///
+    /// Please note that the minimum rating used by [is_encoded_equals](Encoder::is_encoded_equals)
+    /// is designed for codex of at most 6 characters.
+    ///
+    /// # Parameter
+    ///
+    /// * `codex_keep`: number of leading and trailing characters to keep.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `codex_keep` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, MatchRatingApproach};
+    ///
+    /// let match_rating = MatchRatingApproach;
+    /// assert_eq!(match_rating.encode("Alexzander"), "ALXNDR");
+    /// assert_eq!(match_rating.encode("Christopherson"), "CHRRSN");
+    ///
+    /// let match_rating = MatchRatingApproach.with_codex_keep(4);
+    /// assert_eq!(match_rating.encode("Christopherson"), "CHRSHRSN");
+    /// ```
+    pub fn with_codex_keep(self, codex_keep: usize) -> CustomMatchRatingApproach {
+        CustomMatchRatingApproach::default().with_codex_keep(codex_keep)
+    }
+
+    /// Encode a full name. The codex is computed over the concatenation of
+    /// `first` and `last`, after removing whitespaces and punctuation, so it is
+    /// the same as encoding `"{first} {last}"`.
+    ///
+    /// If one of the names is empty, the codex is the one of the other name.
+    ///
+    /// # Parameters
+    ///
+    /// * `first`: first name.
+    /// * `last`: last name.
+    ///
+    /// # Return
+    ///
+    /// The codex of the full name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, MatchRatingApproach};
+    ///
+    /// let match_rating = MatchRatingApproach;
+    ///
+    /// assert_eq!(match_rating.encode_full_name("John", "Smith"), "JHNMTH");
+    /// assert!(match_rating.is_encoded_equals("John Smith", "Jon Smyth"));
+    /// ```
+    pub fn encode_full_name(&self, first: &str, last: &str) -> String {
+        self.encode(&MatchRatingApproach::full_name(first, last))
+    }
+
+    /// Return the rating string of `value`, that is the string that is compared by
+    /// [is_encoded_equals](Encoder::is_encoded_equals) and [similarity](MatchRatingApproach::similarity) :
+    /// `value` is cleaned (uppercased, without accents, whitespaces and punctuation), then its vowels
+    /// (except the first letter) and double consonants are removed, and only the first and last
+    /// 3 letters (see [with_codex_keep](MatchRatingApproach::with_codex_keep)) of long values are kept.
+    ///
+    /// This is the codex returned by [encode](Encoder::encode), so it can also be used as a
+    /// blocking key.
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to rate.
+    ///
+    /// # Return
+    ///
+    /// The rating string, it is empty if `value` has less than 2 characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::MatchRatingApproach;
+    ///
+    /// let match_rating = MatchRatingApproach;
+    ///
+    /// assert_eq!(match_rating.get_rating("Byrne"), "BYRN");
+    /// assert_eq!(match_rating.get_rating("Boern"), "BRN");
+    /// ```
+    pub fn get_rating(&self, value: &str) -> String {
+        self.encode(value)
+    }
+
+    /// Compute the comparison rating of two values, along with the minimum rating
+    /// they must reach to be considered equal (see [Encoder::is_encoded_equals]).
+    ///
+    /// # Parameters
+    ///
+    /// * `first`: first value.
+    /// * `second`: second value.
+    ///
+    /// # Return
+    ///
+    /// A tuple `(rating, minimum_rating)`, or [None] if values can't be compared, that is
+    /// if a value has less than 2 characters or if their codes lengths differ by 3 or more.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::MatchRatingApproach;
+    ///
+    /// let match_rating = MatchRatingApproach;
+    ///
+    /// assert_eq!(match_rating.similarity("Smith", "Smyth"), Some((5, 3)));
+    /// assert_eq!(match_rating.similarity("Tim", "Timothy"), None);
+    /// ```
+    pub fn similarity(&self, first: &str, second: &str) -> Option<(usize, usize)> {
+        MatchRatingApproach::similarity_with(self, first, second)
+    }
+
+    fn full_name(first: &str, last: &str) -> String {
+        let mut full_name = String::with_capacity(first.len() + last.len() + 1);
+        full_name.push_str(first.trim());
+        full_name.push(' ');
+        full_name.push_str(last.trim());
+
+        full_name
+    }
+
+    fn similarity_with(encoder: &dyn Encoder, first: &str, second: &str) -> Option<(usize, usize)> {
+        if first.trim().len() <= 1 || second.trim().len() <= 1 {
+            return None;
+        }
+
+        let name1 = encoder.encode(first);
+        let name2 = encoder.encode(second);
+
+        if name1.is_empty() || name2.is_empty() || name1.len().abs_diff(name2.len()) >= 3 {
+            return None;
+        }
+
+        let sum_length = name1.len() + name2.len();
+
+        let min_rating = MatchRatingApproach::get_minimum_rating(sum_length);
+        let count = MatchRatingApproach::left_to_right_then_right_to_left_processing(name1, name2);
+
+        Some((count, min_rating))
+    }
+
+    fn is_encoded_equals_with(encoder: &dyn Encoder, first: &str, second: &str) -> bool {
+        if first.trim().is_empty() || second.trim().is_empty() {
+            return false;
+        }
+
+        if first.trim().len() == 1 || second.trim().len() == 1 {
+            return false;
+        }
+
+        if first == second {
+            return true;
+        }
+
+        MatchRatingApproach::similarity_with(encoder, first, second)
+            .map(|(count, min_rating)| count >= min_rating)
+            .unwrap_or(false)
+    }
+
+    /// Codex before keeping only its first and last characters.
+    fn full_codex(value: &str) -> String {
+        if value.trim().is_empty() || value.trim().len() == 1 {
+            return String::new();
+        }
+
+        // We can do clean_name and remove_vowels in one pass, but I keep for the
+        // moment the same as commons-codec.
+        let value = MatchRatingApproach::clean_name(value);
+        let value = MatchRatingApproach::remove_vowels(value);
+        MatchRatingApproach::remove_double_consonants(value)
+    }
+
     fn clean_name(value: &str) -> String {
         let result = value.to_uppercase();
 
@@ -130,8 +315,12 @@ impl MatchRatingApproach {
     }
 
     fn get_first3_last3(value: String) -> String {
-        if value.len() > 6 {
-            format!("{}{}", &value[0..3], &value[value.len() - 3..])
+        MatchRatingApproach::get_first_last(value, DEFAULT_CODEX_KEEP)
+    }
+
+    fn get_first_last(value: String, keep: usize) -> String {
+        if value.len() > 2 * keep {
+            format!("{}{}", &value[0..keep], &value[value.len() - keep..])
         } else {
             value
         }
@@ -187,44 +376,108 @@ impl MatchRatingApproach {
 
 impl Encoder for MatchRatingApproach {
     fn encode(&self, value: &str) -> String {
-        if value.trim().is_empty() || value.trim().len() == 1 {
-            return String::new();
-        }
-
-        // We can do clean_name and remove_vowels in one pass, but I keep for the
-        // moment the same as commons-codec.
-        let value = MatchRatingApproach::clean_name(value);
-        let value = MatchRatingApproach::remove_vowels(value);
-        let value = MatchRatingApproach::remove_double_consonants(value);
-        MatchRatingApproach::get_first3_last3(value)
+        MatchRatingApproach::get_first3_last3(MatchRatingApproach::full_codex(value))
     }
 
     fn is_encoded_equals(&self, first: &str, second: &str) -> bool {
-        if first.trim().is_empty() || second.trim().is_empty() {
-            return false;
-        }
+        MatchRatingApproach::is_encoded_equals_with(self, first, second)
+    }
+}
 
-        if first.trim().len() == 1 || second.trim().len() == 1 {
-            return false;
-        }
+/// This is a [MatchRatingApproach] that keeps a custom number of leading and trailing
+/// characters of long codex, see [with_codex_keep](MatchRatingApproach::with_codex_keep).
+///
+/// Its [Default] implementation keeps 3 characters, as [MatchRatingApproach].
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{CustomMatchRatingApproach, Encoder};
+///
+/// let match_rating = CustomMatchRatingApproach::default().with_codex_keep(5);
+/// assert_eq!(match_rating.encode("Christopherson"), "CHRSTPHRSN");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "CustomMatchRatingApproachParameters")]
+pub struct CustomMatchRatingApproach {
+    codex_keep: usize,
+}
 
-        if first == second {
-            return true;
+#[derive(Deserialize)]
+struct CustomMatchRatingApproachParameters {
+    #[serde(default = "default_codex_keep")]
+    codex_keep: usize,
+}
+
+fn default_codex_keep() -> usize {
+    DEFAULT_CODEX_KEEP
+}
+
+impl TryFrom<CustomMatchRatingApproachParameters> for CustomMatchRatingApproach {
+    type Error = PhoneticError;
+
+    fn try_from(parameters: CustomMatchRatingApproachParameters) -> Result<Self, Self::Error> {
+        if parameters.codex_keep == 0 {
+            return Err(PhoneticError::InvalidParameter {
+                name: "codex_keep".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
         }
 
-        let name1 = self.encode(first);
-        let name2 = self.encode(second);
+        Ok(Self {
+            codex_keep: parameters.codex_keep,
+        })
+    }
+}
 
-        if name1.len().abs_diff(name2.len()) >= 3 {
-            return false;
+impl Default for CustomMatchRatingApproach {
+    fn default() -> Self {
+        Self {
+            codex_keep: DEFAULT_CODEX_KEEP,
         }
+    }
+}
 
-        let sum_length = name1.len() + name2.len();
+impl CustomMatchRatingApproach {
+    /// Set how many leading and trailing characters are kept, see
+    /// [MatchRatingApproach::with_codex_keep].
+    ///
+    /// # Parameter
+    ///
+    /// * `codex_keep`: number of leading and trailing characters to keep.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `codex_keep` is `0`.
+    pub fn with_codex_keep(mut self, codex_keep: usize) -> Self {
+        assert!(codex_keep > 0, "codex_keep must be greater than 0");
+        self.codex_keep = codex_keep;
+        self
+    }
 
-        let min_rating = MatchRatingApproach::get_minimum_rating(sum_length);
-        let count = MatchRatingApproach::left_to_right_then_right_to_left_processing(name1, name2);
+    /// Encode a full name, see [MatchRatingApproach::encode_full_name].
+    pub fn encode_full_name(&self, first: &str, last: &str) -> String {
+        self.encode(&MatchRatingApproach::full_name(first, last))
+    }
+
+    /// Return the rating string of `value`, see [MatchRatingApproach::get_rating].
+    pub fn get_rating(&self, value: &str) -> String {
+        self.encode(value)
+    }
+
+    /// Compute the comparison rating of two values, see [MatchRatingApproach::similarity].
+    pub fn similarity(&self, first: &str, second: &str) -> Option<(usize, usize)> {
+        MatchRatingApproach::similarity_with(self, first, second)
+    }
+}
 
-        count >= min_rating
+impl Encoder for CustomMatchRatingApproach {
+    fn encode(&self, value: &str) -> String {
+        MatchRatingApproach::get_first_last(MatchRatingApproach::full_codex(value), self.codex_keep)
+    }
+
+    fn is_encoded_equals(&self, first: &str, second: &str) -> bool {
+        MatchRatingApproach::is_encoded_equals_with(self, first, second)
     }
 }
 
@@ -363,6 +616,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_rating() {
+        let match_rating = MatchRatingApproach;
+
+        assert_eq!(match_rating.get_rating("Byrne"), "BYRN");
+        assert_eq!(match_rating.get_rating("Boern"), "BRN");
+        assert_eq!(match_rating.get_rating("Smith"), "SMTH");
+        assert_eq!(match_rating.get_rating("Catherine"), "CTHRN");
+        assert_eq!(match_rating.get_rating("Kathryn"), "KTHRYN");
+        assert_eq!(match_rating.get_rating("Mississippi"), "MSSP");
+        assert_eq!(match_rating.get_rating("Alexander"), "ALXNDR");
+        assert_eq!(match_rating.get_rating("Christopherson"), "CHRRSN");
+        assert_eq!(match_rating.get_rating(" B "), "");
+        assert_eq!(
+            match_rating.get_rating("Byrne"),
+            match_rating.encode("Byrne")
+        );
+    }
+
+    #[test]
+    fn test_encode_full_name() {
+        let encoder = MatchRatingApproach;
+
+        let codex = encoder.encode_full_name("John", "Smith");
+        assert_eq!(codex, "JHNMTH");
+        assert_eq!(encoder.encode_full_name(" John ", "Smith "), codex);
+        assert_eq!(encoder.encode("John Smith"), codex);
+
+        assert_eq!(encoder.encode_full_name("", "Smith"), "SMTH");
+        assert_eq!(encoder.encode_full_name("John", ""), "JHN");
+        assert_eq!(encoder.encode_full_name("", ""), "");
+
+        assert!(encoder.is_encoded_equals("John Smith", "Jon Smyth"));
+        assert!(!encoder.is_encoded_equals("John Smith", "Karl Alessandro"));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let match_rating = MatchRatingApproach;
+
+        assert_eq!(match_rating.similarity("Smith", "Smyth"), Some((5, 3)));
+        assert_eq!(match_rating.similarity("Smyth", "Smith"), Some((5, 3)));
+        assert_eq!(match_rating.similarity("Smith", "Smith"), Some((6, 3)));
+        assert_eq!(
+            match_rating.similarity("Franciszek", "Frances"),
+            Some((3, 3))
+        );
+        assert!(match_rating.is_encoded_equals("Franciszek", "Frances"));
+
+        // Rejected for length
+        assert_eq!(match_rating.similarity("Tim", "Timothy"), None);
+        assert_eq!(match_rating.similarity("Karl", "Alessandro"), None);
+        assert!(!match_rating.is_encoded_equals("Tim", "Timothy"));
+        assert_eq!(match_rating.similarity("", "Smith"), None);
+        assert_eq!(match_rating.similarity("Smith", "S"), None);
+    }
+
+    #[test]
+    fn test_with_codex_keep() {
+        let name = "Wolfeschlegelsteinhausenbergerdorff";
+
+        let encoder = MatchRatingApproach;
+        assert_eq!(encoder.encode(name), "WLFDRF");
+
+        let encoder = encoder.with_codex_keep(5);
+        assert_eq!(encoder.encode(name), "WLFSCGRDRF");
+        assert_eq!(encoder.encode("Smith"), "SMTH");
+
+        let encoder = encoder.with_codex_keep(100);
+        assert_eq!(encoder.encode(name), "WLFSCHLGLSTNHSNBRGRDRF");
+        assert_eq!(
+            encoder.with_codex_keep(3).encode(name),
+            MatchRatingApproach.encode(name)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_codex_keep_zero() {
+        let _ = MatchRatingApproach.with_codex_keep(0);
+    }
+
+    #[test]
+    fn test_deserialize_custom() {
+        let encoder: CustomMatchRatingApproach =
+            serde_json::from_str(r#"{"codex_keep": 4}"#).unwrap();
+        assert_eq!(encoder, MatchRatingApproach.with_codex_keep(4));
+        let encoder: CustomMatchRatingApproach = serde_json::from_str("{}").unwrap();
+        assert_eq!(encoder, CustomMatchRatingApproach::default());
+        assert!(serde_json::from_str::<CustomMatchRatingApproach>(r#"{"codex_keep": 0}"#).is_err());
+    }
+
     #[test]
     fn test_left_to_right_then_right_to_left_alexander_alexandra_returns_4() {
         assert_eq!(
@@ -14,10 +14,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use alloc::string::{String, ToString};
+
 use serde::{Deserialize, Serialize};
 
 use crate::helper::is_vowel;
-use crate::Encoder;
+use crate::{Encoder, SoundexCommons};
 
 const FRONTV: &str = "EIY";
 const VARSON: &str = "CSPTG";
@@ -51,6 +53,37 @@ impl Metaphone {
         Self { max_code_length }
     }
 
+    /// Check if `value1` and `value2` have the same [Metaphone] code, like
+    /// commons-codec's `isMetaphoneEqual`.
+    ///
+    /// See [difference](SoundexCommons::difference) to get the number of
+    /// characters at the same position in both codes.
+    ///
+    /// # Parameters
+    ///
+    /// * `value1` and `value2`: values to check.
+    ///
+    /// # Return
+    ///
+    /// Return `true` if both codes are equals.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Metaphone, SoundexCommons};
+    ///
+    /// let metaphone = Metaphone::default();
+    ///
+    /// assert!(metaphone.metaphone_difference("Thomson", "Thomsen"));
+    /// assert!(!metaphone.metaphone_difference("Thomson", "Johnson"));
+    ///
+    /// // 0MSN and JNSN
+    /// assert_eq!(metaphone.difference("Thomson", "Johnson"), 2);
+    /// ```
+    pub fn metaphone_difference(&self, value1: &str, value2: &str) -> bool {
+        self.encode(value1) == self.encode(value2)
+    }
+
     fn is_vowel(text: &str, index: usize) -> bool {
         let ch = text.chars().nth(index).map(|c| c.to_ascii_lowercase());
         is_vowel(ch, false)
@@ -65,7 +98,13 @@ impl Metaphone {
     }
 
     fn region_match(text: &str, index: usize, test: &str) -> bool {
-        index + test.len() - 1 < text.len() && text[index..].contains(test)
+        // `index` is a char index, `text` may contain non ASCII characters
+        match text.char_indices().nth(index) {
+            Some((start, _)) => {
+                index + test.len() - 1 < text.chars().count() && text[start..].contains(test)
+            }
+            None => false,
+        }
     }
 
     fn is_last_char(wdsz: usize, n: usize) -> bool {
@@ -82,11 +121,56 @@ impl Default for Metaphone {
     }
 }
 
+/// This is a builder for [Metaphone].
+///
+/// Its [Default] implementation has a maximum code length of 4, as [Metaphone]'s.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Encoder, MetaphoneBuilder};
+///
+/// let metaphone = MetaphoneBuilder::default().max_code_length(None).build();
+///
+/// assert_eq!(metaphone.encode("discrimination"), "TSKRMNXN");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MetaphoneBuilder {
+    max_code_length: Option<usize>,
+}
+
+impl Default for MetaphoneBuilder {
+    fn default() -> Self {
+        Self {
+            max_code_length: Some(4),
+        }
+    }
+}
+
+impl MetaphoneBuilder {
+    /// Set the maximum code length.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_code_length`: the maximum code length. If you provide [Option::None]
+    ///   then the resulting code can be of any length.
+    pub fn max_code_length(mut self, max_code_length: Option<usize>) -> Self {
+        self.max_code_length = max_code_length;
+
+        self
+    }
+
+    /// Construct a new [Metaphone] encoder.
+    pub fn build(self) -> Metaphone {
+        Metaphone::new(self.max_code_length)
+    }
+}
+
 impl Encoder for Metaphone {
     fn encode(&self, value: &str) -> String {
-        let inwd = value.to_uppercase();
+        let inwd = value.trim().to_uppercase();
 
-        if inwd.len() == 1 {
+        if inwd.chars().count() == 1 {
             return inwd;
         }
 
@@ -124,7 +208,8 @@ impl Encoder for Metaphone {
             _ => local.push_str(&inwd),
         }
 
-        let wdsz = local.len();
+        // Indexes below are char indexes
+        let wdsz = local.chars().count();
 
         let mut skip = 0;
         for (index, symb) in local.chars().enumerate() {
@@ -139,17 +224,11 @@ impl Encoder for Metaphone {
                 }
                 if symb == 'C' || !Metaphone::is_previous_char(&local, index, symb) {
                     match symb {
-                        'A' | 'E' | 'I' | 'O' | 'U' => {
-                            if index == 0 {
-                                code.push(symb);
-                            }
-                        }
-                        'B' => {
-                            if !Metaphone::is_previous_char(&local, index, 'M')
-                                || !Metaphone::is_last_char(wdsz, index)
-                            {
-                                code.push(symb);
-                            }
+                        'A' | 'E' | 'I' | 'O' | 'U' if index == 0 => code.push(symb),
+                        'B' if !Metaphone::is_previous_char(&local, index, 'M')
+                            || !Metaphone::is_last_char(wdsz, index) =>
+                        {
+                            code.push(symb)
                         }
                         'C' => {
                             let next = local.chars().nth(index + 1);
@@ -225,10 +304,8 @@ impl Encoder for Metaphone {
                             }
                         }
                         'F' | 'J' | 'L' | 'M' | 'N' | 'R' => code.push(symb),
-                        'K' => {
-                            if index == 0 || !Metaphone::is_previous_char(&local, index, 'C') {
-                                code.push(symb);
-                            }
+                        'K' if index == 0 || !Metaphone::is_previous_char(&local, index, 'C') => {
+                            code.push(symb)
                         }
                         'P' => {
                             if Metaphone::is_next_char(&local, index, 'H') {
@@ -262,12 +339,11 @@ impl Encoder for Metaphone {
                             }
                         }
                         'V' => code.push('F'),
-                        'W' | 'Y' => {
+                        'W' | 'Y'
                             if !Metaphone::is_last_char(wdsz, index)
-                                && Metaphone::is_vowel(&local, index + 1)
-                            {
-                                code.push(symb);
-                            }
+                                && Metaphone::is_vowel(&local, index + 1) =>
+                        {
+                            code.push(symb)
                         }
                         'X' => {
                             code.push('K');
@@ -295,10 +371,64 @@ impl Encoder for Metaphone {
     }
 }
 
+/// [Metaphone] codes are compared position by position, see
+/// [metaphone_difference](Metaphone::metaphone_difference) to only check equality.
+impl SoundexCommons for Metaphone {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_empty() {
+        let metaphone = Metaphone::default();
+
+        assert_eq!(metaphone.encode(""), "");
+        assert_eq!(metaphone.encode(" "), "");
+        assert_eq!(metaphone.encode("\t\n"), "");
+        assert_eq!(metaphone.encode("\t\n\r "), "");
+    }
+
+    #[test]
+    fn test_one_letter() {
+        let metaphone = Metaphone::default();
+
+        for letter in 'A'..='Z' {
+            assert_eq!(metaphone.encode(&letter.to_string()), letter.to_string());
+            assert_eq!(
+                metaphone.encode(&letter.to_ascii_lowercase().to_string()),
+                letter.to_string()
+            );
+        }
+        assert_eq!(metaphone.encode(" b "), "B");
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let metaphone = Metaphone::default();
+
+        assert_eq!(metaphone.encode("ÉDG"), "TK");
+        assert_eq!(metaphone.encode("ÉGE"), "J");
+        assert_eq!(metaphone.encode("ÀDGE"), "J");
+        // A single letter is kept, as commons-codec does
+        assert_eq!(metaphone.encode("é"), "É");
+        assert_eq!(metaphone.encode(" É "), "É");
+    }
+
+    #[test]
+    fn test_metaphone_difference() {
+        let metaphone = Metaphone::default();
+
+        // 0MPS and TMPS, `TH` is coded as `0`
+        assert!(!metaphone.metaphone_difference("Thompson", "Tompson"));
+        assert_eq!(metaphone.difference("Thompson", "Tompson"), 3);
+        assert!(metaphone.metaphone_difference("Thomson", "Thomsen"));
+        assert_eq!(metaphone.difference("Thomson", "Thomsen"), 4);
+        // 0MSN and JNSN
+        assert_eq!(metaphone.difference("Thomson", "Johnson"), 2);
+        assert_eq!(metaphone.difference("Thompson", ""), 0);
+    }
+
     #[test]
     fn test_is_metaphone_equal1() {
         let metaphone = Metaphone::default();
@@ -682,6 +812,24 @@ mod tests {
         assert_eq!(result, "ALRTN");
     }
 
+    #[test]
+    fn test_builder() {
+        assert_eq!(MetaphoneBuilder::default().build(), Metaphone::default());
+
+        let bounded = MetaphoneBuilder::default().build();
+        assert_eq!(bounded.encode("discrimination"), "TSKR");
+        assert_eq!(bounded.encode("AXEAXE"), "AKSK");
+
+        let bounded = MetaphoneBuilder::default().max_code_length(Some(6)).build();
+        assert_eq!(bounded.encode("AXEAXEAXE"), "AKSKSK");
+
+        let unbounded = MetaphoneBuilder::default().max_code_length(None).build();
+        assert_eq!(unbounded, Metaphone::new(None));
+        assert_eq!(unbounded.encode("discrimination"), "TSKRMNXN");
+        assert_eq!(unbounded.encode("synchronization"), "SNXRNSXN");
+        assert_eq!(unbounded.encode("AXEAXEAXE"), "AKSKSKS");
+    }
+
     #[test]
     fn test_unbounded_2() {
         let encoder = Metaphone::new(None);
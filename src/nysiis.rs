@@ -14,7 +14,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use serde::{Deserialize, Serialize};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::helper::is_vowel;
 use crate::{Encoder, SoundexUtils};
@@ -45,11 +48,51 @@ const END_ND: &str = "ND";
 
 const TRUE_LENGTH: usize = 6;
 
+fn default_preserve_initial_vowel() -> bool {
+    true
+}
+
+/// Deserialize a field that is present, even if it is `null`, as `Some`.
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Serialized fields of [Nysiis], it also accepts the `strict` field of previous versions.
+#[derive(Deserialize)]
+struct NysiisParameters {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    max_length: Option<Option<usize>>,
+    #[serde(default)]
+    strict: Option<bool>,
+    #[serde(default = "default_preserve_initial_vowel")]
+    preserve_initial_vowel: bool,
+}
+
+impl From<NysiisParameters> for Nysiis {
+    fn from(parameters: NysiisParameters) -> Self {
+        let max_length = match (parameters.max_length, parameters.strict) {
+            (Some(max_length), _) => max_length,
+            (None, Some(false)) => None,
+            (None, _) => Some(TRUE_LENGTH),
+        };
+
+        Self {
+            max_length,
+            preserve_initial_vowel: parameters.preserve_initial_vowel,
+        }
+    }
+}
+
 /// This the [Nysiis](https://en.wikipedia.org/wiki/New_York_State_Identification_and_Intelligence_System) algorithm.
 ///
 /// [Default] implementation constructs a strict version of the generated code.
 /// That means the code has at most 6 characters.
-/// A `new` constructor is provided, allowing code to have more than 6 characters.
+/// A `new` constructor is provided, allowing code to have another maximum length,
+/// or no maximum length at all ("true" NYSIIS). See also [NysiisBuilder].
 ///
 /// ```rust
 /// use rphonetic::{Nysiis, Encoder};
@@ -59,22 +102,80 @@ const TRUE_LENGTH: usize = 6;
 /// assert_eq!(nysiis.encode("WESTERLUND"),"WASTAR");
 ///
 /// // Not strict
-/// let nysiis = Nysiis::new(false);
+/// let nysiis = Nysiis::new(None);
 /// assert_eq!(nysiis.encode("WESTERLUND"),"WASTARLAD");
 /// ```
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(from = "NysiisParameters")]
 pub struct Nysiis {
-    strict: bool,
+    max_length: Option<usize>,
+    preserve_initial_vowel: bool,
 }
 
 impl Nysiis {
-    /// Use this constructor to disable the maximum code length.
+    /// Use this constructor to change or disable the maximum code length.
+    ///
+    /// Codes are truncated once all the rules are applied.
     ///
     /// # Parameter
     ///
-    /// * `strict`: if `true` code will have maximum length of 6.
-    pub fn new(strict: bool) -> Self {
-        Self { strict }
+    /// * `max_length`: the maximum code length. If you provide [Option::None]
+    ///   then the resulting code can be of any length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Nysiis};
+    ///
+    /// assert_eq!(Nysiis::new(Some(6)).encode("MacIntosh"), "MCANT");
+    /// assert_eq!(Nysiis::new(Some(3)).encode("MacIntosh"), "MCA");
+    /// ```
+    pub fn new(max_length: Option<usize>) -> Self {
+        Self {
+            max_length,
+            preserve_initial_vowel: true,
+        }
+    }
+
+    /// Compute the number of characters that are at the same place in both codes,
+    /// as [SoundexCommons::difference](crate::SoundexCommons::difference) does.
+    ///
+    /// As codes can have different lengths (up to 6 by default, without limit if there's no maximum length),
+    /// the result can be greater than 4.
+    ///
+    /// # Parameters
+    ///
+    /// * `value1` : first value
+    /// * `value2` : second value
+    ///
+    /// # Return
+    ///
+    /// The number of characters at the same position. 0 indicates no similarities or
+    /// that one of the code is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Nysiis;
+    ///
+    /// let nysiis = Nysiis::new(None);
+    ///
+    /// assert_eq!(nysiis.difference("MacDonald", "McDonald"), 8);
+    /// assert_eq!(nysiis.difference("Smith", "Schmidt"), 3);
+    /// ```
+    pub fn difference(&self, value1: &str, value2: &str) -> usize {
+        let value1 = self.encode(value1);
+        let value2 = self.encode(value2);
+
+        if value1.is_empty() || value2.is_empty() {
+            return 0;
+        }
+
+        value1
+            .chars()
+            .zip(value2.chars())
+            .filter(|(ch1, ch2)| ch1 == ch2)
+            .count()
     }
 
     fn transcode(
@@ -122,7 +223,86 @@ impl Nysiis {
 
 impl Default for Nysiis {
     fn default() -> Self {
-        Self { strict: true }
+        Self::new(Some(TRUE_LENGTH))
+    }
+}
+
+/// This is a builder for [Nysiis].
+///
+/// Its [Default] implementation constructs the same encoder as [Nysiis]'s.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Encoder, NysiisBuilder};
+///
+/// let nysiis = NysiisBuilder::default()
+///     .strict(false)
+///     .preserve_initial_vowel(false)
+///     .build();
+///
+/// assert_eq!(nysiis.encode("Edwards"), "ADWARD");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NysiisBuilder {
+    max_length: Option<usize>,
+    preserve_initial_vowel: bool,
+}
+
+impl Default for NysiisBuilder {
+    fn default() -> Self {
+        Self {
+            max_length: Some(TRUE_LENGTH),
+            preserve_initial_vowel: true,
+        }
+    }
+}
+
+impl NysiisBuilder {
+    /// Enable or disable the maximum code length of 6. By default, it is enabled.
+    /// It replaces the length set with [max_length](NysiisBuilder::max_length).
+    ///
+    /// # Parameter
+    ///
+    /// * `strict`: if `true` code will have maximum length of 6.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.max_length = strict.then_some(TRUE_LENGTH);
+
+        self
+    }
+
+    /// Set the maximum code length. By default, it is 6.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_length`: the maximum code length. If you provide [Option::None]
+    ///   then the resulting code can be of any length.
+    pub fn max_length(mut self, max_length: Option<usize>) -> Self {
+        self.max_length = max_length;
+
+        self
+    }
+
+    /// Enable or disable the preservation of an initial vowel. By default, as in
+    /// the original algorithm, the first letter of the code is the first letter of the
+    /// value. When disabled, an initial vowel is normalized to `A`, as vowels are in the
+    /// rest of the value.
+    ///
+    /// # Parameter
+    ///
+    /// * `preserve_initial_vowel`: if `false`, an initial vowel becomes `A`.
+    pub fn preserve_initial_vowel(mut self, preserve_initial_vowel: bool) -> Self {
+        self.preserve_initial_vowel = preserve_initial_vowel;
+
+        self
+    }
+
+    /// Construct a new [Nysiis] encoder.
+    pub fn build(self) -> Nysiis {
+        Nysiis {
+            max_length: self.max_length,
+            preserve_initial_vowel: self.preserve_initial_vowel,
+        }
     }
 }
 
@@ -168,6 +348,9 @@ impl Encoder for Nysiis {
         result.push(tmp.chars().next().unwrap());
 
         let mut chars: Vec<char> = tmp.chars().collect();
+        if !self.preserve_initial_vowel && is_vowel(Some(chars[0].to_ascii_lowercase()), false) {
+            chars[0] = 'A';
+        }
         let len = chars.len();
         let mut index = 1;
 
@@ -211,18 +394,16 @@ impl Encoder for Nysiis {
             key
         };
 
-        if self.strict {
-            let min = std::cmp::min(result.len(), TRUE_LENGTH);
-            result[..min].to_string()
-        } else {
-            result
+        match self.max_length {
+            Some(max_length) => result.chars().take(max_length).collect(),
+            None => result,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Encoder, Nysiis};
+    use crate::{Encoder, Nysiis, NysiisBuilder};
 
     fn encode_all(values: Vec<&str>, expected: &str) {
         let nysiis = Nysiis::default();
@@ -232,7 +413,7 @@ mod tests {
     }
 
     fn encode(values: Vec<(&str, &str)>) {
-        let nysiis = Nysiis::new(false);
+        let nysiis = Nysiis::new(None);
         for (value, expected) in values {
             assert_eq!(
                 nysiis.encode(value),
@@ -242,6 +423,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builder() {
+        assert_eq!(NysiisBuilder::default().build(), Nysiis::default());
+        assert_eq!(
+            NysiisBuilder::default().strict(false).build(),
+            Nysiis::new(None)
+        );
+        assert_eq!(
+            NysiisBuilder::default().max_length(Some(4)).build(),
+            Nysiis::new(Some(4))
+        );
+        assert_eq!(
+            NysiisBuilder::default()
+                .max_length(Some(4))
+                .strict(true)
+                .build(),
+            Nysiis::default()
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let data = [
+            (r#"{"strict":true}"#, Nysiis::default()),
+            (r#"{"strict":false}"#, Nysiis::new(None)),
+            (
+                r#"{"strict":true,"preserve_initial_vowel":false}"#,
+                NysiisBuilder::default()
+                    .preserve_initial_vowel(false)
+                    .build(),
+            ),
+            (r#"{"max_length":4}"#, Nysiis::new(Some(4))),
+            (r#"{"max_length":null}"#, Nysiis::new(None)),
+            (r#"{"max_length":4,"strict":false}"#, Nysiis::new(Some(4))),
+            ("{}", Nysiis::default()),
+        ];
+        for (json, expected) in data {
+            let nysiis: Nysiis = serde_json::from_str(json).unwrap();
+            assert_eq!(nysiis, expected, "{json}");
+        }
+
+        for nysiis in [Nysiis::default(), Nysiis::new(None), Nysiis::new(Some(3))] {
+            let json = serde_json::to_string(&nysiis).unwrap();
+            assert_eq!(serde_json::from_str::<Nysiis>(&json).unwrap(), nysiis);
+        }
+    }
+
+    #[test]
+    fn test_max_length() {
+        let strict = Nysiis::new(Some(6));
+        let unlimited = Nysiis::new(None);
+
+        assert_eq!(strict, Nysiis::default());
+        assert_eq!(strict.encode("MacIntosh"), "MCANT");
+        assert_eq!(unlimited.encode("MacIntosh"), "MCANT");
+        // Truncation happens once all the rules are applied, so the last `A` is kept
+        assert_eq!(strict.encode("MacIntoshire"), "MCANTA");
+        assert_eq!(unlimited.encode("MacIntoshire"), "MCANTASAR");
+        assert_eq!(Nysiis::new(Some(3)).encode("MacIntosh"), "MCA");
+        assert_eq!(Nysiis::new(Some(0)).encode("MacIntosh"), "");
+    }
+
+    #[test]
+    fn test_preserve_initial_vowel() {
+        let classic = NysiisBuilder::default().strict(false).build();
+        let normalized = NysiisBuilder::default()
+            .strict(false)
+            .preserve_initial_vowel(false)
+            .build();
+
+        assert_eq!(classic.encode("Edwards"), "EDWARD");
+        assert_eq!(normalized.encode("Edwards"), "ADWARD");
+        assert_eq!(classic.encode("Olsen"), "OLSAN");
+        assert_eq!(normalized.encode("Olsen"), "ALSAN");
+        assert_eq!(classic.encode("Ivan"), "IVAN");
+        assert_eq!(normalized.encode("Ivan"), "AVAN");
+
+        // Already an `A`
+        assert_eq!(classic.encode("Aaron"), "ARAN");
+        assert_eq!(normalized.encode("Aaron"), "ARAN");
+
+        // Not a vowel
+        assert_eq!(normalized.encode("Brian"), classic.encode("Brian"));
+        assert_eq!(normalized.encode("Yolanda"), classic.encode("Yolanda"));
+        assert!(normalized.is_encoded_equals("Edwards", "Adwards"));
+        assert!(!classic.is_encoded_equals("Edwards", "Adwards"));
+    }
+
+    #[test]
+    fn test_difference() {
+        let nysiis = Nysiis::new(None);
+        assert_eq!(nysiis.encode("MacDonald"), "MCDANALD");
+        assert_eq!(nysiis.encode("McDonald"), "MCDANALD");
+        assert_eq!(nysiis.difference("MacDonald", "McDonald"), 8);
+        assert_eq!(nysiis.difference("Smith", "Schmidt"), 3);
+        assert_eq!(nysiis.difference("Stevenson", "Stephenson"), 9);
+        assert_eq!(nysiis.difference("Catherine", "Kathryn"), 3);
+        assert_eq!(nysiis.difference("Margaret", "Andrew"), 0);
+        assert_eq!(nysiis.difference("", "McDonald"), 0);
+        assert_eq!(nysiis.difference("McDonald", ""), 0);
+        assert_eq!(nysiis.difference("", ""), 0);
+
+        let nysiis = Nysiis::default();
+        assert_eq!(nysiis.difference("MacDonald", "McDonald"), 6);
+    }
+
     #[test]
     fn test_bran() {
         encode_all(vec!["Brian", "Brown", "Brun"], "BRAN");
@@ -14,10 +14,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use alloc::string::{String, ToString};
+
 use serde::{Deserialize, Serialize};
 
 use crate::helper::is_vowel;
-use crate::{Encoder, SoundexUtils};
+use crate::{Encoder, PhoneticError, SoundexUtils};
 
 /// Phonex is a modification of the venerable Soundex algorithm. It accounts
 /// for a few more letter combinations to improve accuracy on some data sets.
@@ -32,10 +34,32 @@ use crate::{Encoder, SoundexUtils};
 /// assert_eq!(phonex.encode("KNUTH"),"N300");
 /// ```
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "PhonexParameters")]
 pub struct Phonex {
     max_code_length: usize,
 }
 
+/// Serialized fields of [Phonex], the code length is checked on deserialization.
+#[derive(Deserialize)]
+struct PhonexParameters {
+    max_code_length: usize,
+}
+
+impl TryFrom<PhonexParameters> for Phonex {
+    type Error = PhoneticError;
+
+    fn try_from(parameters: PhonexParameters) -> Result<Self, Self::Error> {
+        if parameters.max_code_length == 0 {
+            return Err(PhoneticError::InvalidParameter {
+                name: "max_code_length".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        Ok(Self::new(parameters.max_code_length))
+    }
+}
+
 impl Phonex {
     /// Construct a new [Phonex] with the maximum code length provided.
     ///
@@ -46,6 +70,35 @@ impl Phonex {
         Self { max_code_length }
     }
 
+    /// Set the length of codes, as [Soundex::max_length](crate::Soundex::max_length) :
+    /// the first letter followed by up to `max_length - 1` codes, padded with `0`.
+    /// By default, it is 4.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_length`: the length of codes.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `max_length` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Phonex};
+    ///
+    /// let phonex = Phonex::default();
+    /// assert_eq!(phonex.encode("Hammerschlag"), "A524");
+    ///
+    /// let phonex = Phonex::default().max_length(6);
+    /// assert_eq!(phonex.encode("Hammerschlag"), "A52420");
+    /// ```
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        assert!(max_length > 0, "max_length must be greater than 0");
+        self.max_code_length = max_length;
+        self
+    }
+
     fn preprocess(&self, value: &str) -> String {
         let mut input = Self::soundex_clean(value);
 
@@ -381,6 +434,67 @@ mod tests {
         ]);
     }
 
+    /// Examples of Lait and Randell paper, as in abydos tests (https://github.com/chrislit/abydos).
+    #[test]
+    fn test_lait_randell() {
+        encode(vec![
+            ("Ewell", "A400"),
+            ("Filp", "F100"),
+            ("Heames", "A500"),
+            ("Kneves", "N100"),
+            ("River", "R160"),
+            ("Corley", "C400"),
+            ("Carton", "C350"),
+            ("Cachpole", "C214"),
+        ]);
+
+        let phonex = Phonex::default();
+        for (first, second) in [
+            ("Ewell", "Ule"),
+            ("Filp", "Philp"),
+            ("Yule", "Ewell"),
+            ("Heames", "Eames"),
+            ("Kneves", "Neves"),
+            ("River", "Rivers"),
+            ("Corley", "Coley"),
+            ("Carton", "Carlton"),
+            ("Cachpole", "Catchpole"),
+        ] {
+            assert!(
+                phonex.is_encoded_equals(first, second),
+                "{first} and {second} should have the same code"
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_length() {
+        let phonex = Phonex::default().max_length(6);
+
+        assert_eq!(phonex, Phonex::new(6));
+        assert_eq!(phonex.encode("Hammerschlag"), "A52420");
+        assert_eq!(phonex.encode("Lukasiewicz"), "L20000");
+        assert_eq!(phonex.encode("Heilbronn"), "A16500");
+        assert_eq!(phonex.encode(""), "000000");
+        assert_eq!(Phonex::default().max_length(1).encode("Knuth"), "N");
+        assert_eq!(Phonex::new(4).max_length(4), Phonex::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "max_length must be greater than 0")]
+    fn test_max_length_zero() {
+        let _ = Phonex::default().max_length(0);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let phonex: Phonex = serde_json::from_str(r#"{"max_code_length":6}"#).unwrap();
+        assert_eq!(phonex, Phonex::new(6));
+
+        let result: Result<Phonex, _> = serde_json::from_str(r#"{"max_code_length":0}"#);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_encode_number() {
         let encoder = Phonex::default();
@@ -0,0 +1,423 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use alloc::string::{String, ToString};
+
+use serde::{Deserialize, Serialize};
+
+use crate::helper::soundex_clean;
+use crate::{Encoder, PhoneticError};
+
+const VOWELS: &str = "AEIOU";
+const CONSONANTS: &str = "BCDFGHJKLMNPQRSTVWXYZ";
+
+/// Where a [Rule] pattern has to be found in the word.
+#[derive(Copy, Clone, Debug)]
+enum Position {
+    /// At the beginning of the word.
+    Start,
+    /// At the end of the word.
+    End,
+    /// Neither the first nor the last letter.
+    Middle,
+    /// Anywhere, all occurrences are replaced.
+    Any,
+}
+
+/// A transformation applied before coding. `pattern` is replaced by `replacement`
+/// when found at `position`, preceded by one of the letters of `before` and followed
+/// by one of the letters of `after` (if any).
+#[derive(Copy, Clone, Debug)]
+struct Rule {
+    position: Position,
+    pattern: &'static str,
+    replacement: &'static str,
+    before: Option<&'static str>,
+    after: Option<&'static str>,
+}
+
+impl Rule {
+    const fn new(position: Position, pattern: &'static str, replacement: &'static str) -> Self {
+        Self {
+            position,
+            pattern,
+            replacement,
+            before: None,
+            after: None,
+        }
+    }
+
+    const fn start(pattern: &'static str, replacement: &'static str) -> Self {
+        Self::new(Position::Start, pattern, replacement)
+    }
+
+    const fn end(pattern: &'static str, replacement: &'static str) -> Self {
+        Self::new(Position::End, pattern, replacement)
+    }
+
+    const fn middle(pattern: &'static str, replacement: &'static str) -> Self {
+        Self::new(Position::Middle, pattern, replacement)
+    }
+
+    const fn any(pattern: &'static str, replacement: &'static str) -> Self {
+        Self::new(Position::Any, pattern, replacement)
+    }
+
+    const fn before(mut self, letters: &'static str) -> Self {
+        self.before = Some(letters);
+        self
+    }
+
+    const fn after(mut self, letters: &'static str) -> Self {
+        self.after = Some(letters);
+        self
+    }
+
+    fn is_matching(&self, word: &str, start: usize, end: usize) -> bool {
+        let position = match self.position {
+            Position::Start => start == 0,
+            Position::End => end == word.len(),
+            Position::Middle => start > 0 && end < word.len(),
+            Position::Any => true,
+        };
+        let before = match self.before {
+            Some(letters) => word[..start]
+                .chars()
+                .next_back()
+                .map_or(false, |c| letters.contains(c)),
+            None => true,
+        };
+        let after = match self.after {
+            Some(letters) => word[end..]
+                .chars()
+                .next()
+                .map_or(false, |c| letters.contains(c)),
+            None => true,
+        };
+
+        position && before && after
+    }
+
+    fn apply(&self, word: &str) -> String {
+        let mut result = String::with_capacity(word.len());
+        let mut index = 0;
+        while let Some(found) = word[index..].find(self.pattern) {
+            let start = index + found;
+            let end = start + self.pattern.len();
+            if self.is_matching(word, start, end) {
+                result.push_str(&word[index..start]);
+                result.push_str(self.replacement);
+                index = end;
+            } else {
+                // Patterns are ASCII, first letter is one byte long
+                result.push_str(&word[index..=start]);
+                index = start + 1;
+            }
+        }
+        result.push_str(&word[index..]);
+
+        result
+    }
+}
+
+/// Transformations, in the order they are applied.
+static RULES: &[Rule] = &[
+    Rule::any("DG", "G"),
+    Rule::any("CO", "KO"),
+    Rule::any("CA", "KA"),
+    Rule::any("CU", "KU"),
+    Rule::any("CY", "SI"),
+    Rule::any("CI", "SI"),
+    Rule::any("CE", "SE"),
+    Rule::start("CL", "KL").after(VOWELS),
+    Rule::any("CK", "K"),
+    Rule::end("GC", "K"),
+    Rule::end("JC", "K"),
+    Rule::start("CHR", "KR").after(VOWELS),
+    Rule::start("CR", "KR").after(VOWELS),
+    Rule::start("WR", "R"),
+    Rule::any("NC", "NK"),
+    Rule::any("CT", "KT"),
+    Rule::any("PH", "F"),
+    Rule::any("AA", "AR"),
+    Rule::any("SCH", "SH"),
+    Rule::any("BTL", "TL"),
+    Rule::any("GHT", "T"),
+    Rule::any("AUGH", "ARF"),
+    Rule::middle("LJ", "LD").before(VOWELS).after(VOWELS),
+    Rule::any("LOUGH", "LOW"),
+    Rule::start("Q", "KW"),
+    Rule::start("KN", "N"),
+    Rule::end("GN", "N"),
+    Rule::any("GHN", "N"),
+    Rule::end("GNE", "N"),
+    Rule::any("GHNE", "NE"),
+    Rule::end("GNES", "NS"),
+    Rule::start("GN", "N"),
+    Rule::middle("GN", "N").after(CONSONANTS),
+    Rule::start("PS", "S"),
+    Rule::start("PT", "T"),
+    Rule::start("CZ", "C"),
+    Rule::middle("WZ", "Z").before(VOWELS),
+    Rule::middle("CZ", "CH"),
+    Rule::any("LZ", "LSH"),
+    Rule::any("RZ", "RSH"),
+    Rule::middle("Z", "S").after(VOWELS),
+    Rule::any("ZZ", "TS"),
+    Rule::middle("Z", "TS").before(CONSONANTS),
+    Rule::any("HROUG", "REW"),
+    Rule::any("OUGH", "OF"),
+    Rule::middle("Q", "KW").before(VOWELS).after(VOWELS),
+    Rule::middle("J", "Y").before(VOWELS).after(VOWELS),
+    Rule::start("YJ", "Y").after(VOWELS),
+    Rule::start("GH", "G"),
+    Rule::end("GH", "E").before(VOWELS),
+    Rule::any("NX", "NKS"),
+    Rule::start("PF", "F"),
+    Rule::end("DT", "T"),
+    Rule::end("TL", "TIL"),
+    Rule::end("DL", "DIL"),
+    Rule::any("YTH", "ITH"),
+    Rule::start("TJ", "CH").after(VOWELS),
+    Rule::start("TSJ", "CH").after(VOWELS),
+    Rule::start("TS", "T").after(VOWELS),
+    Rule::any("TCH", "CH"),
+    Rule::middle("WSK", "VSKIE").before(VOWELS),
+    Rule::end("WSK", "VSKIE").before(VOWELS),
+    Rule::start("MN", "N").after(VOWELS),
+    Rule::start("PN", "N").after(VOWELS),
+    Rule::middle("STL", "SL").before(VOWELS),
+    Rule::end("STL", "SL").before(VOWELS),
+    Rule::end("TNT", "ENT"),
+    Rule::end("EAUX", "OH"),
+    Rule::any("EXCI", "ECS"),
+    Rule::any("X", "ECS"),
+    Rule::end("NED", "ND"),
+    Rule::any("JR", "DR"),
+    Rule::end("EE", "EA"),
+    Rule::any("ZS", "S"),
+    Rule::middle("R", "AH").before(VOWELS).after(CONSONANTS),
+    Rule::middle("HR", "AH").before(VOWELS).after(CONSONANTS),
+    Rule::end("HR", "AH").before(VOWELS),
+    Rule::end("RE", "AR"),
+    Rule::end("R", "AH").before(VOWELS),
+    Rule::any("LLE", "LE"),
+    Rule::end("LE", "ILE").before(CONSONANTS),
+    Rule::end("LES", "ILES").before(CONSONANTS),
+    Rule::end("E", ""),
+    Rule::end("ES", "S"),
+    Rule::end("SS", "AS").before(VOWELS),
+    Rule::end("MB", "M").before(VOWELS),
+    Rule::any("MPTS", "MPS"),
+    Rule::any("MPS", "MS"),
+    Rule::any("MPT", "MT"),
+];
+
+/// This is the [Phonix](https://www.researchgate.net/publication/220308114_PHONIX_The_algorithm) algorithm,
+/// designed by T.N. Gadd. It applies about ninety letter group transformations
+/// (eg. `GHT` becomes `T`, a final `R` after a vowel becomes `AH`) before coding letters
+/// as [Soundex](crate::Soundex) does, with its own groups.
+///
+/// As in the reference implementations, a first letter that is a vowel or `Y` is
+/// coded `v`.
+///
+/// ```rust
+/// use rphonetic::{Encoder, Phonix};
+///
+/// let phonix = Phonix::default();
+/// assert_eq!(phonix.encode("Knight"), "N300");
+/// assert_eq!(phonix.encode("Night"), "N300");
+/// ```
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "PhonixParameters")]
+pub struct Phonix {
+    max_code_length: usize,
+}
+
+/// Serialized fields of [Phonix], the code length is checked on deserialization.
+#[derive(Deserialize)]
+struct PhonixParameters {
+    max_code_length: usize,
+}
+
+impl TryFrom<PhonixParameters> for Phonix {
+    type Error = PhoneticError;
+
+    fn try_from(parameters: PhonixParameters) -> Result<Self, Self::Error> {
+        if parameters.max_code_length == 0 {
+            return Err(PhoneticError::InvalidParameter {
+                name: "max_code_length".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        Ok(Self::new(parameters.max_code_length))
+    }
+}
+
+impl Phonix {
+    /// Construct a new [Phonix] with the maximum code length provided.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_code_length`: the maximum code length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Phonix};
+    ///
+    /// let phonix = Phonix::new(5);
+    /// assert_eq!(phonix.encode("Computers"), "K5138");
+    /// ```
+    pub fn new(max_code_length: usize) -> Self {
+        Self { max_code_length }
+    }
+
+    fn code(c: char) -> char {
+        match c {
+            'B' | 'P' => '1',
+            'C' | 'G' | 'J' | 'K' | 'Q' => '2',
+            'D' | 'T' => '3',
+            'L' => '4',
+            'M' | 'N' => '5',
+            'R' => '6',
+            'F' | 'V' => '7',
+            'S' | 'X' | 'Z' => '8',
+            _ => '0',
+        }
+    }
+}
+
+impl Default for Phonix {
+    fn default() -> Self {
+        Self { max_code_length: 4 }
+    }
+}
+
+impl Encoder for Phonix {
+    fn encode(&self, value: &str) -> String {
+        let word = RULES
+            .iter()
+            .fold(soundex_clean(value), |word, rule| rule.apply(&word));
+
+        let mut chars = word.chars();
+        let mut result = String::with_capacity(self.max_code_length);
+        let mut last = match chars.next() {
+            Some(c) if "AEIOUY".contains(c) => 'v',
+            Some(c) => c,
+            None => '0',
+        };
+        if last != '0' {
+            result.push(last);
+        }
+        for code in chars.map(Self::code) {
+            if code != last && code != '0' {
+                result.push(code);
+            }
+            // Repeated codes are removed before zeros, so a vowel separates them
+            last = code;
+        }
+
+        let mut result: String = result.chars().take(self.max_code_length).collect();
+        while result.chars().count() < self.max_code_length {
+            result.push('0');
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(Rule::any("GHT", "T").apply("KNIGHT"), "KNIT");
+        assert_eq!(Rule::start("KN", "N").apply("KNIKNAK"), "NIKNAK");
+        assert_eq!(Rule::end("E", "").apply("EWE"), "EW");
+        assert_eq!(Rule::middle("Z", "S").after(VOWELS).apply("ZAZAZ"), "ZASAZ");
+        assert_eq!(Rule::end("R", "AH").before(VOWELS).apply("MEYER"), "MEYEAH");
+        assert_eq!(Rule::end("R", "AH").before(VOWELS).apply("HERR"), "HERR");
+        assert_eq!(Rule::end("SS", "AS").before(VOWELS).apply("BOSSS"), "BOSSS");
+    }
+
+    #[test]
+    fn test_encode() {
+        // From abydos tests (https://github.com/chrislit/abydos)
+        let phonix = Phonix::default();
+        let data = [
+            ("computer", "K513"),
+            ("computers", "K513"),
+            ("pfeifer", "F700"),
+            ("pfeiffer", "F700"),
+            ("knight", "N300"),
+            ("night", "N300"),
+        ];
+        for (value, expected) in data {
+            assert_eq!(phonix.encode(value), expected, "{value}");
+        }
+
+        // From talisman tests (https://github.com/Yomguithereal/talisman), with codes of length 8
+        let phonix = Phonix::new(8);
+        let data = [
+            ("Guillaume", "G4500000"),
+            ("Ewell", "v4000000"),
+            ("Filp", "F4100000"),
+            ("Heames", "H5800000"),
+            ("Kneves", "N7800000"),
+            ("River", "R7000000"),
+            ("Corley", "K4000000"),
+            ("Carton", "K3500000"),
+            ("Cachpole", "K2140000"),
+        ];
+        for (value, expected) in data {
+            assert_eq!(phonix.encode(value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_first_vowel() {
+        let phonix = Phonix::default();
+
+        assert_eq!(phonix.encode("Ashcraft"), phonix.encode("Ishcraft"));
+        assert!(phonix.encode("Ashcraft").starts_with('v'));
+    }
+
+    #[test]
+    fn test_max_code_length() {
+        assert_eq!(Phonix::new(5).encode("computers"), "K5138");
+        assert_eq!(Phonix::new(2).encode("computers"), "K5");
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let phonix: Phonix = serde_json::from_str(r#"{"max_code_length":8}"#).unwrap();
+        assert_eq!(phonix, Phonix::new(8));
+
+        let result: Result<Phonix, _> = serde_json::from_str(r#"{"max_code_length":0}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_empty() {
+        let phonix = Phonix::default();
+
+        assert_eq!(phonix.encode(""), "0000");
+        assert_eq!(phonix.encode(" 42 "), "0000");
+    }
+}
@@ -14,11 +14,19 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::str::FromStr;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Encoder, SoundexCommons, SoundexUtils};
+use crate::soundex::parse_mapping;
+#[cfg(feature = "smol_str")]
+use crate::{encode_smol_str, SmolStr};
+use crate::{
+    soundex_letters, Encoder, NonLetterPolicy, PhoneticError, SoundexCommons, SoundexUtils,
+    SEPARATOR,
+};
 
 const ENGLISH_MAPPING: [char; 26] = [
     '0', '1', '3', '6', '0', '2', '4', '0', '0', '4', '3', '7', '8', '8', '0', '1', '5', '9', '3',
@@ -36,10 +44,20 @@ const ENGLISH_MAPPING: [char; 26] = [
 /// let refined_soundex = RefinedSoundex::default();
 ///
 /// assert_eq!(refined_soundex.encode("jumped"), "J408106");
+///
+/// // Lowercase codes
+/// let refined_soundex = RefinedSoundex::default().lowercase_output(true);
+/// assert_eq!(refined_soundex.encode("jumped"), "j408106");
 /// ```
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct RefinedSoundex {
     mapping: [char; 26],
+    #[serde(default)]
+    lowercase_output: bool,
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    non_letter_policy: NonLetterPolicy,
 }
 
 impl RefinedSoundex {
@@ -54,11 +72,166 @@ impl RefinedSoundex {
     ///   Index 0 is the code for `A`, index 1
     ///   is for `B`and so on for each letter of the latin alphabet.
     pub fn new(mapping: [char; 26]) -> Self {
-        Self { mapping }
+        Self {
+            mapping,
+            lowercase_output: false,
+            strict: false,
+            non_letter_policy: NonLetterPolicy::Drop,
+        }
+    }
+
+    /// Construct a new [RefinedSoundex] from a mapping string, eg. read from a configuration.
+    ///
+    /// # Parameter
+    ///
+    /// * `mapping`: the code of each letter, from `A` to `Z`. It must contain exactly
+    ///   26 ASCII letters or digits.
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::InvalidMapping] if `mapping` does not contain exactly
+    /// 26 characters, or if a character is neither an ASCII letter nor a digit.
+    /// The reason contains the faulty character and its position (starting at 1).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{Encoder, RefinedSoundex};
+    ///
+    /// let refined_soundex = RefinedSoundex::from_mapping("01360240043788015936020505")?;
+    /// assert_eq!(refined_soundex.encode("jumped"), "J408106");
+    ///
+    /// assert!(RefinedSoundex::from_mapping("0136").is_err());
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn from_mapping(mapping: &str) -> Result<Self, PhoneticError> {
+        Ok(Self::new(parse_mapping(mapping, false)?))
+    }
+
+    /// Construct a new strict [RefinedSoundex] with the provided mapping.
+    ///
+    /// A strict [RefinedSoundex] works only with ASCII: its mapping must be ASCII
+    /// and [try_encode](RefinedSoundex::try_encode) rejects non-ASCII values instead
+    /// of ignoring non-ASCII characters.
+    ///
+    /// # Parameter
+    ///
+    /// * `mapping`: mapping array, see [new](RefinedSoundex::new).
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::NonAsciiMapping] with the non-ASCII codes of the
+    /// mapping, if any.
+    pub fn strict(mapping: [char; 26]) -> Result<Self, PhoneticError> {
+        let non_ascii: Vec<char> = mapping.iter().copied().filter(|c| !c.is_ascii()).collect();
+        if !non_ascii.is_empty() {
+            return Err(PhoneticError::NonAsciiMapping(non_ascii));
+        }
+
+        let mut refined_soundex = Self::new(mapping);
+        refined_soundex.strict = true;
+        Ok(refined_soundex)
+    }
+
+    /// Encode `value`. If this [RefinedSoundex] is [strict](RefinedSoundex::strict),
+    /// `value` must be ASCII, otherwise it is the same as [encode](Encoder::encode).
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to encode.
+    ///
+    /// # Error
+    ///
+    /// It returns the first non-ASCII character of `value` if this [RefinedSoundex]
+    /// is strict.
+    pub fn try_encode(&self, value: &str) -> Result<String, char> {
+        if self.strict {
+            if let Some(ch) = value.chars().find(|ch| !ch.is_ascii()) {
+                return Err(ch);
+            }
+        }
+
+        Ok(self.encode(value))
+    }
+
+    /// Enable or disable lowercase codes. By default, codes are uppercase.
+    ///
+    /// # Parameter
+    ///
+    /// * `lowercase_output`: if `true`, the letter of the code will be lowercase.
+    pub fn lowercase_output(mut self, lowercase_output: bool) -> Self {
+        self.lowercase_output = lowercase_output;
+        self
+    }
+
+    /// Set how non-letters are handled, see [NonLetterPolicy]. By default,
+    /// they are dropped.
+    ///
+    /// # Parameter
+    ///
+    /// * `non_letter_policy`: the policy to apply.
+    pub fn non_letter_policy(mut self, non_letter_policy: NonLetterPolicy) -> Self {
+        self.non_letter_policy = non_letter_policy;
+        self
+    }
+
+    /// Encode `value` and return a [SmolStr], so no heap allocation
+    /// is required unless the code is long.
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to encode.
+    ///
+    /// # Return
+    ///
+    /// The code, same as [encode](Encoder::encode).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, RefinedSoundex};
+    ///
+    /// let refined_soundex = RefinedSoundex::default();
+    ///
+    /// assert_eq!(refined_soundex.encode_small("jumped"), refined_soundex.encode("jumped"));
+    /// ```
+    #[cfg(feature = "smol_str")]
+    pub fn encode_small(&self, value: &str) -> SmolStr {
+        encode_smol_str(|output| self.encode_with(value, output))
+    }
+
+    fn encode_with<O: Extend<char>>(&self, value: &str, code: &mut O) {
+        let mut letters = soundex_letters(value, self.non_letter_policy).peekable();
+        let first = match letters.peek() {
+            None => return,
+            Some(first) if self.lowercase_output => first.to_ascii_lowercase(),
+            Some(first) => *first,
+        };
+        code.extend(Some(first));
+
+        let mut previous: Option<char> = None;
+
+        for ch in letters {
+            let code_value = match ch {
+                SEPARATOR => '0',
+                _ => self.get_mapping_code(ch),
+            };
+            if Some(code_value) != previous {
+                code.extend(Some(code_value));
+            }
+            previous = Some(code_value);
+        }
     }
 
+    /// Letters outside of `A..Z` (eg. `É`) have no mapping, they are coded
+    /// like vowels.
     fn get_mapping_code(&self, ch: char) -> char {
-        self.mapping[ch as usize - 65]
+        self.mapping
+            .get((ch as usize).wrapping_sub('A' as usize))
+            .copied()
+            .unwrap_or('0')
     }
 }
 
@@ -88,7 +261,7 @@ impl FromStr for RefinedSoundex {
     /// ```
     fn from_str(mapping: &str) -> Result<Self, Self::Err> {
         let mapping: [char; 26] = mapping.chars().collect::<Vec<char>>().try_into()?;
-        Ok(Self { mapping })
+        Ok(Self::new(mapping))
     }
 }
 
@@ -118,7 +291,7 @@ impl TryFrom<&str> for RefinedSoundex {
     /// ```
     fn try_from(mapping: &str) -> Result<Self, Self::Error> {
         let mapping: [char; 26] = mapping.chars().collect::<Vec<char>>().try_into()?;
-        Ok(Self { mapping })
+        Ok(Self::new(mapping))
     }
 }
 
@@ -153,34 +326,21 @@ impl TryFrom<String> for RefinedSoundex {
 
 impl Default for RefinedSoundex {
     fn default() -> Self {
-        Self {
-            mapping: ENGLISH_MAPPING,
-        }
+        Self::new(ENGLISH_MAPPING)
     }
 }
 
 impl Encoder for RefinedSoundex {
     fn encode(&self, value: &str) -> String {
-        let value = Self::soundex_clean(value);
-        if value.is_empty() {
-            return value;
-        }
-
-        let mut code = String::with_capacity(value.len() + 1);
-        code.push(value.chars().next().unwrap());
-
-        let mut previous: Option<char> = None;
-
-        for ch in value.chars() {
-            let code_value = self.get_mapping_code(ch);
-            if Some(code_value) != previous {
-                code.push(code_value);
-            }
-            previous = Some(code_value);
-        }
-
+        let mut code = String::new();
+        self.encode_into(value, &mut code);
         code
     }
+
+    fn encode_into(&self, value: &str, out: &mut String) {
+        out.clear();
+        self.encode_with(value, out);
+    }
 }
 
 impl SoundexUtils for RefinedSoundex {}
@@ -190,6 +350,7 @@ impl SoundexCommons for RefinedSoundex {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::soundex_fixtures::SOUNDEX_TEST_DATA;
 
     #[test]
     fn test_difference() {
@@ -225,6 +386,118 @@ mod tests {
         assert_eq!(refined_soundex.encode("dogs"), "D6043");
     }
 
+    #[test]
+    fn test_lowercase_output() {
+        let refined_soundex = RefinedSoundex::default().lowercase_output(true);
+
+        assert_eq!(refined_soundex.encode("Robert"), "r901096");
+        assert_eq!(refined_soundex.encode("jumped"), "j408106");
+        assert_eq!(refined_soundex.encode(""), "");
+    }
+
+    #[test]
+    fn test_non_letter_policy() {
+        let refined_soundex = RefinedSoundex::default();
+        assert_eq!(refined_soundex.encode("Van-Dyke"), "V2086030");
+
+        let refined_soundex = refined_soundex.non_letter_policy(NonLetterPolicy::Separator);
+        assert_eq!(refined_soundex.encode("Van-Dyke"), "V20806030");
+        assert_eq!(refined_soundex.encode("Van - Dyke"), "V20806030");
+        // Separator after a vowel is merged
+        assert_eq!(refined_soundex.encode("Ana-Lee"), "A08070");
+        assert_eq!(refined_soundex.encode(" Vandyke "), "V2086030");
+    }
+
+    #[cfg(feature = "smol_str")]
+    #[test]
+    fn test_encode_small() {
+        let refined_soundexes = [
+            RefinedSoundex::default(),
+            RefinedSoundex::default().lowercase_output(true),
+            RefinedSoundex::default().non_letter_policy(NonLetterPolicy::Separator),
+        ];
+        let values = ["", "Robert", "jumped", "Van-Dyke", "O'Hara", "  ", "123"];
+
+        for refined_soundex in refined_soundexes.iter() {
+            for value in values {
+                let code = refined_soundex.encode_small(value);
+                assert_eq!(code, refined_soundex.encode(value), "Error for {value:?}");
+                assert!(!code.is_heap_allocated());
+            }
+        }
+
+        let value = "Bartholomew".repeat(3);
+        let code = RefinedSoundex::default().encode_small(&value);
+        assert_eq!(code, RefinedSoundex::default().encode(&value));
+        assert!(code.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_from_mapping() -> Result<(), PhoneticError> {
+        let refined_soundex = RefinedSoundex::from_mapping("01360240043788015936020505")?;
+        assert_eq!(refined_soundex, RefinedSoundex::default());
+
+        // Identity mapping
+        let refined_soundex = RefinedSoundex::from_mapping("ABCDEFGHIJKLMNOPQRSTUVWXYZ")?;
+        assert_eq!(refined_soundex.encode("Robert"), "RROBERT");
+        assert_eq!(refined_soundex.encode("Lloyd"), "LLOYD");
+        assert_eq!(refined_soundex.difference("Robert", "Rupert"), 5);
+        assert_eq!(refined_soundex.difference("Lloyd", "Robert"), 1);
+        assert_eq!(refined_soundex.difference("Lloyd", ""), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_mapping_errors() {
+        match RefinedSoundex::from_mapping("ABC") {
+            Err(PhoneticError::InvalidMapping { reason }) => {
+                assert_eq!(reason, "mapping must contain 26 codes, found 3");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        match RefinedSoundex::from_mapping("ABCDEFGHIJKLMNOPQRSTUVWXY") {
+            Err(error @ PhoneticError::InvalidMapping { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "Invalid mapping : mapping must contain 26 codes, found 25"
+                );
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        match RefinedSoundex::from_mapping("ABCDEFGHIJKLMNOPQRSTUVWXY-") {
+            Err(PhoneticError::InvalidMapping { reason }) => {
+                assert_eq!(reason, "invalid code '-' for letter 'Z' at position 26");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        assert!(RefinedSoundex::from_mapping("").is_err());
+    }
+
+    #[test]
+    fn test_strict() -> Result<(), PhoneticError> {
+        let refined_soundex = RefinedSoundex::strict(ENGLISH_MAPPING)?;
+
+        assert_eq!(refined_soundex.try_encode("José"), Err('é'));
+        assert_eq!(
+            refined_soundex.try_encode("jumped"),
+            Ok("J408106".to_string())
+        );
+
+        let mut mapping = ENGLISH_MAPPING;
+        mapping[1] = 'β';
+        mapping[3] = 'δ';
+        assert_eq!(
+            RefinedSoundex::strict(mapping),
+            Err(PhoneticError::NonAsciiMapping(vec!['β', 'δ']))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_new() {
         assert_eq!(
@@ -248,4 +521,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn check_refined_soundex_fixture() {
+        let refined_soundex = RefinedSoundex::default();
+
+        for (i, (value, _, expected)) in SOUNDEX_TEST_DATA.iter().enumerate() {
+            assert_eq!(
+                refined_soundex.encode(value),
+                *expected,
+                "[{i}] {value} fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_into() {
+        let encoder = RefinedSoundex::default();
+        let mut buffer = String::from("previous content");
+
+        for (value, _, _) in SOUNDEX_TEST_DATA.iter() {
+            encoder.encode_into(value, &mut buffer);
+            assert_eq!(buffer, encoder.encode(value), "{value} fail");
+        }
+        encoder.encode_into("", &mut buffer);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_encode_opt() {
+        let encoder = RefinedSoundex::default();
+
+        assert_eq!(encoder.encode_opt("Robert"), Some(encoder.encode("Robert")));
+        assert_eq!(encoder.encode_opt("123"), None);
+        assert_eq!(encoder.encode_opt(""), None);
+        assert_eq!(encoder.encode_opt(" -. "), None);
+    }
 }
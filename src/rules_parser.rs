@@ -111,6 +111,21 @@ pub fn language<'a>() -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
     terminated(alpha1, end_of_line())
 }
 
+/// Try to explain why the first line of `remains` is not a valid quadruplet rule.
+///
+/// It returns `None` if the line doesn't look like a quadruplet at all.
+pub fn quadruplet_error(remains: &str) -> Option<String> {
+    let line = remains.split('\n').next().unwrap_or_default();
+    let quotes = line.replace("\\\"", "").matches('"').count();
+    if quotes % 2 == 1 {
+        Some("Unterminated quote".to_string())
+    } else if quotes > 0 && quotes != 8 {
+        Some(format!("Expected 4 quoted parts, found {}", quotes / 2))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -180,6 +195,23 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_quadruplet_error() {
+        assert_eq!(
+            quadruplet_error("\"part1\" \"part2\" \"part3\" \"part4\nOther \"data\""),
+            Some("Unterminated quote".to_string())
+        );
+        assert_eq!(
+            quadruplet_error("\"part1\" \"part2\" \"part3\""),
+            Some("Expected 4 quoted parts, found 3".to_string())
+        );
+        assert_eq!(
+            quadruplet_error("\"part1\" \"\\\"\" \"part3\""),
+            Some("Expected 4 quoted parts, found 3".to_string())
+        );
+        assert_eq!(quadruplet_error("This is wrong."), None);
+    }
+
     #[test]
     fn test_quadruplet_failing() {
         let result: IResult<&str, (&str, &str, &str, &str)> =
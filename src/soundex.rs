@@ -14,13 +14,24 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::iter::{once, repeat};
+use core::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Encoder, SoundexCommons, SoundexUtils};
+use crate::helper::{fnv1a_64, tokenize_name};
+use crate::{build_error, PhoneticError};
+#[cfg(feature = "smol_str")]
+use crate::{encode_smol_str, SmolStr};
+use crate::{soundex_letters, Encoder, NonLetterPolicy, SoundexCommons, SoundexUtils, SEPARATOR};
 
 const SILENT: char = '-';
+const CODE_LENGTH: usize = 4;
+const PADDING: char = '0';
 
 /// This is the default mapping character for soundex.
 /// * `A` is encoded into `0`
@@ -53,13 +64,85 @@ pub const DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX: [char; 26] = [
     '3', '-', '1', '-', '2', '-', '2',
 ];
 
+/// This enum tells [Soundex] how to handle `H` and `W` between two consonants
+/// that have the same code.
+///
+/// # Example
+///
+/// ```rust
+/// use rphonetic::{Encoder, HwRule, Soundex};
+///
+/// let soundex = Soundex::default();
+/// assert_eq!(soundex.encode("Ashcraft"), "A261");
+///
+/// let soundex = Soundex::default().hw_rule(HwRule::Separator);
+/// assert_eq!(soundex.encode("Ashcraft"), "A226");
+/// ```
+#[derive(
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum HwRule {
+    /// `H` and `W` are ignored, so consonants around them with the same code are coded once,
+    /// as in standard Soundex and commons-codec (this is the default).
+    #[default]
+    Ignore,
+    /// `H` and `W` are treated like vowels, so consonants around them are both coded.
+    Separator,
+}
+
 fn has_silent_in_mapping(mapping: [char; 26]) -> bool {
     mapping.iter().any(|c| c == &SILENT)
 }
 
+/// Parse a mapping string into a mapping array. Codes must be ASCII letters or digits,
+/// or [SILENT] if `allow_silent` is `true`.
+pub(crate) fn parse_mapping(
+    mapping: &str,
+    allow_silent: bool,
+) -> Result<[char; 26], PhoneticError> {
+    let length = mapping.chars().count();
+    if length != 26 {
+        return Err(PhoneticError::InvalidMapping {
+            reason: format!("mapping must contain 26 codes, found {length}"),
+        });
+    }
+
+    let mut result: [char; 26] = ['0'; 26];
+    for (position, (code, letter)) in mapping.chars().zip('A'..='Z').enumerate() {
+        let is_silent = allow_silent && code == SILENT;
+        if !code.is_ascii_alphanumeric() && !is_silent {
+            return Err(PhoneticError::InvalidMapping {
+                reason: format!(
+                    "invalid code '{code}' for letter '{letter}' at position {}",
+                    position + 1
+                ),
+            });
+        }
+        result[position] = code;
+    }
+
+    Ok(result)
+}
+
+/// Build a table that contains, for each ASCII letter byte (either lowercase or
+/// uppercase), its code. Other bytes are `0`.
+///
+/// It returns [None] if there's a code that is not ASCII.
+fn build_ascii_table(mapping: [char; 26]) -> Option<[u8; 256]> {
+    let mut table = [0u8; 256];
+    for (index, code) in mapping.iter().enumerate() {
+        if !code.is_ascii() || *code == '\0' {
+            return None;
+        }
+        table[b'A' as usize + index] = *code as u8;
+        table[b'a' as usize + index] = *code as u8;
+    }
+    Some(table)
+}
+
 /// This is the [Soundex](https://en.wikipedia.org/wiki/Soundex) implementation of [Encoder].
 ///
-/// The code will have a constant length of 4.
+/// By default, the code will have a constant length of 4, see [max_length](Soundex::max_length).
 ///
 /// Although it was primarily done for names, [Soundex] can be used for general words.
 ///
@@ -70,11 +153,89 @@ fn has_silent_in_mapping(mapping: [char; 26]) -> bool {
 ///
 /// let soundex = Soundex::default();
 /// assert_eq!(soundex.encode("jumped"), "J513");
+///
+/// // Lowercase codes
+/// let soundex = Soundex::default().lowercase_output(true);
+/// assert_eq!(soundex.encode("jumped"), "j513");
 /// ```
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "SoundexParameters")]
 pub struct Soundex {
     mapping: [char; 26],
     special_case_h_w: bool,
+    lowercase_output: bool,
+    strict: bool,
+    non_letter_policy: NonLetterPolicy,
+    normalize_initial: bool,
+    legacy_first_group: bool,
+    encode_first_letter: bool,
+    max_length: usize,
+    padding: bool,
+    /// `H` and `W` are coded `0`, whatever their mapping is (see [HwRule::Separator]).
+    hw_separator: bool,
+    /// Precomputed from `mapping` for ASCII values.
+    #[serde(skip_serializing)]
+    ascii_table: Option<[u8; 256]>,
+}
+
+fn default_max_length() -> usize {
+    CODE_LENGTH
+}
+
+fn default_padding() -> bool {
+    true
+}
+
+/// Serializable fields of [Soundex], the ASCII table is rebuilt on deserialization.
+#[derive(Deserialize)]
+struct SoundexParameters {
+    mapping: [char; 26],
+    special_case_h_w: bool,
+    #[serde(default)]
+    lowercase_output: bool,
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    non_letter_policy: NonLetterPolicy,
+    #[serde(default)]
+    normalize_initial: bool,
+    #[serde(default)]
+    legacy_first_group: bool,
+    #[serde(default)]
+    encode_first_letter: bool,
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+    #[serde(default = "default_padding")]
+    padding: bool,
+    #[serde(default)]
+    hw_separator: bool,
+}
+
+impl TryFrom<SoundexParameters> for Soundex {
+    type Error = PhoneticError;
+
+    fn try_from(parameters: SoundexParameters) -> Result<Self, Self::Error> {
+        if parameters.max_length == 0 {
+            return Err(PhoneticError::InvalidParameter {
+                name: "max_length".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let mut soundex = Self::new(parameters.mapping, parameters.special_case_h_w)
+            .lowercase_output(parameters.lowercase_output)
+            .non_letter_policy(parameters.non_letter_policy)
+            .normalize_initial(parameters.normalize_initial)
+            .legacy_first_group(parameters.legacy_first_group)
+            .encode_first_letter(parameters.encode_first_letter)
+            .max_length(parameters.max_length)
+            .padding(parameters.padding);
+        if parameters.hw_separator {
+            soundex = soundex.hw_rule(HwRule::Separator);
+        }
+        soundex.strict = parameters.strict;
+        Ok(soundex)
+    }
 }
 
 impl Soundex {
@@ -94,11 +255,588 @@ impl Soundex {
         Self {
             mapping,
             special_case_h_w,
+            lowercase_output: false,
+            strict: false,
+            non_letter_policy: NonLetterPolicy::Drop,
+            normalize_initial: false,
+            legacy_first_group: false,
+            encode_first_letter: false,
+            max_length: CODE_LENGTH,
+            padding: true,
+            hw_separator: false,
+            ascii_table: build_ascii_table(mapping),
+        }
+    }
+
+    /// Construct a new [Soundex] from group of letters and their code. `H` and `W`
+    /// are treated as for [From] implementation.
+    ///
+    /// # Parameter
+    ///
+    /// * `groups`: pairs of letters and their code. Every letter of the latin
+    ///   alphabet must be in exactly one group, case is ignored. A code must be
+    ///   one character, `-` for silent.
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::ParseRuleError] if a code is not a single
+    /// character, if a group contains something else than a letter, or if a letter is in zero
+    /// or multiple groups. The line number of the error is the position of the faulty group
+    /// (starting at 1), or 0 if some letters are missing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::from_groups(&[
+    ///     ("AEIOUYHW", "0"),
+    ///     ("BFPV", "1"),
+    ///     ("CGJKQSXZ", "2"),
+    ///     ("DT", "3"),
+    ///     ("L", "4"),
+    ///     ("MN", "5"),
+    ///     ("R", "6"),
+    /// ])?;
+    ///
+    /// assert_eq!(soundex.encode("jumped"), "J513");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn from_groups(groups: &[(&str, &str)]) -> Result<Self, PhoneticError> {
+        let mut mapping: [Option<char>; 26] = [None; 26];
+
+        for (index, (letters, code)) in groups.iter().enumerate() {
+            let line = format!("(\"{letters}\", \"{code}\")");
+            let mut code_chars = code.chars();
+            let code = match (code_chars.next(), code_chars.next()) {
+                (Some(code), None) => code,
+                _ => {
+                    return Err(build_error(
+                        index + 1,
+                        None,
+                        &line,
+                        "Code must be exactly one character".to_string(),
+                    ))
+                }
+            };
+
+            for letter in letters.chars() {
+                if !letter.is_ascii_alphabetic() {
+                    return Err(build_error(
+                        index + 1,
+                        None,
+                        &line,
+                        format!("'{letter}' is not a letter"),
+                    ));
+                }
+                let position = (letter.to_ascii_uppercase() as u8 - b'A') as usize;
+                if mapping[position].is_some() {
+                    return Err(build_error(
+                        index + 1,
+                        None,
+                        &line,
+                        format!("Letter '{letter}' is already in a group"),
+                    ));
+                }
+                mapping[position] = Some(code);
+            }
+        }
+
+        let missing: String = mapping
+            .iter()
+            .zip('A'..='Z')
+            .filter(|(code, _)| code.is_none())
+            .map(|(_, letter)| letter)
+            .collect();
+        if !missing.is_empty() {
+            return Err(build_error(
+                0,
+                None,
+                &missing,
+                "Letters are not in any group".to_string(),
+            ));
+        }
+
+        Ok(Self::from(mapping.map(|code| code.unwrap())))
+    }
+
+    /// Construct a new [Soundex] with the provided mapping and code length.
+    /// `H` and `W` are treated as for [From] implementation.
+    ///
+    /// # Parameters
+    ///
+    /// * `mapping`: mapping array, see [new](Soundex::new).
+    /// * `max_length`: the length of codes, see [max_length](Soundex::max_length).
+    ///
+    /// # Panics
+    ///
+    /// It panics if `max_length` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex, DEFAULT_US_ENGLISH_MAPPING_SOUNDEX};
+    ///
+    /// let soundex = Soundex::with_length(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX, 6);
+    ///
+    /// assert_eq!(soundex.encode("Washington"), "W25235");
+    /// ```
+    pub fn with_length(mapping: [char; 26], max_length: usize) -> Self {
+        Self::from(mapping).max_length(max_length)
+    }
+
+    /// Construct a new [Soundex] from a mapping string, eg. read from a configuration.
+    /// `H` and `W` are treated as for [From] implementation.
+    ///
+    /// # Parameter
+    ///
+    /// * `mapping`: the code of each letter, from `A` to `Z`. It must contain exactly
+    ///   26 ASCII letters or digits, or `-` for silent.
+    ///
+    /// # Error
+    ///
+    /// It returns a [PhoneticError::InvalidMapping] if `mapping` does not contain exactly
+    /// 26 characters, or if a character is neither an ASCII letter, a digit nor `-`.
+    /// The reason contains the faulty character and its position (starting at 1).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), rphonetic::PhoneticError> {
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::from_mapping("01230120022455012623010202")?;
+    /// assert_eq!(soundex.encode("jumped"), "J513");
+    ///
+    /// assert!(Soundex::from_mapping("0123").is_err());
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn from_mapping(mapping: &str) -> Result<Self, PhoneticError> {
+        Ok(Self::from(parse_mapping(mapping, true)?))
+    }
+
+    /// Construct a new strict [Soundex] with the provided mapping. `H` and `W`
+    /// are treated as for [From] implementation.
+    ///
+    /// A strict [Soundex] works only with ASCII: its mapping must be ASCII
+    /// and [try_encode](Soundex::try_encode) rejects non-ASCII values instead
+    /// of ignoring non-ASCII characters.
+    ///
+    /// # Parameter
+    ///
+    /// * `mapping`: mapping array, see [new](Soundex::new).
+    ///
+    /// # Error
+    ///
+    /// It returns the mapping if it contains non-ASCII codes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Vec<char>> {
+    /// use rphonetic::{Soundex, DEFAULT_US_ENGLISH_MAPPING_SOUNDEX};
+    ///
+    /// let soundex = Soundex::strict(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX)?;
+    ///
+    /// assert_eq!(soundex.try_encode("Jose"), Ok("J200".to_string()));
+    /// assert_eq!(soundex.try_encode("José"), Err('é'));
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn strict(mapping: [char; 26]) -> Result<Self, Vec<char>> {
+        if !mapping.iter().all(char::is_ascii) {
+            return Err(mapping.to_vec());
+        }
+
+        let mut soundex = Self::from(mapping);
+        soundex.strict = true;
+        Ok(soundex)
+    }
+
+    /// Encode `value`. If this [Soundex] is [strict](Soundex::strict),
+    /// `value` must be ASCII, otherwise it is the same as [encode](Encoder::encode).
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to encode.
+    ///
+    /// # Error
+    ///
+    /// It returns the first non-ASCII character of `value` if this [Soundex]
+    /// is strict.
+    pub fn try_encode(&self, value: &str) -> Result<String, char> {
+        if self.strict {
+            if let Some(ch) = value.chars().find(|ch| !ch.is_ascii()) {
+                return Err(ch);
+            }
+        }
+
+        Ok(self.encode(value))
+    }
+
+    /// Enable or disable lowercase codes. By default, codes are uppercase.
+    ///
+    /// # Parameter
+    ///
+    /// * `lowercase_output`: if `true`, the letter of the code will be lowercase.
+    pub fn lowercase_output(mut self, lowercase_output: bool) -> Self {
+        self.lowercase_output = lowercase_output;
+        self
+    }
+
+    /// Set the length of codes : the first letter followed by up to `max_length - 1`
+    /// codes, padded with `0`. By default, it is 4, as in standard Soundex.
+    ///
+    /// # Parameter
+    ///
+    /// * `max_length`: the length of codes.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `max_length` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert_eq!(soundex.encode("Washington"), "W252");
+    ///
+    /// let soundex = Soundex::default().max_length(6);
+    /// assert_eq!(soundex.encode("Washington"), "W25235");
+    /// assert_eq!(soundex.encode("Lee"), "L00000");
+    /// ```
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        assert!(max_length > 0, "max_length must be greater than 0");
+        self.max_length = max_length;
+        self
+    }
+
+    /// Enable or disable the padding of codes with `0` when the value is too short to
+    /// produce [max_length](Soundex::max_length) characters. By default, it is enabled.
+    ///
+    /// # Parameter
+    ///
+    /// * `padding`: if `false`, codes are not padded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert_eq!(soundex.encode("Rice"), "R200");
+    ///
+    /// let soundex = Soundex::default().padding(false);
+    /// assert_eq!(soundex.encode("Rice"), "R2");
+    /// ```
+    pub fn padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set how non-letters are handled, see [NonLetterPolicy]. By default,
+    /// they are dropped.
+    ///
+    /// # Parameter
+    ///
+    /// * `non_letter_policy`: the policy to apply.
+    pub fn non_letter_policy(mut self, non_letter_policy: NonLetterPolicy) -> Self {
+        self.non_letter_policy = non_letter_policy;
+        self
+    }
+
+    /// Enable or disable the normalization of the first letter. When enabled, the first
+    /// letter of the code is replaced by the first letter of the alphabet that has the same
+    /// mapping code, so that similar-sounding initials share the same code (eg. `Carl` and `Karl`).
+    /// By default, it is disabled, as in standard Soundex.
+    ///
+    /// # Parameter
+    ///
+    /// * `normalize_initial`: if `true`, the first letter is normalized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert_ne!(soundex.encode("Carl"), soundex.encode("Karl"));
+    ///
+    /// let soundex = Soundex::default().normalize_initial(true);
+    /// assert_eq!(soundex.encode("Carl"), "C640");
+    /// assert_eq!(soundex.encode("Karl"), "C640");
+    /// ```
+    pub fn normalize_initial(mut self, normalize_initial: bool) -> Self {
+        self.normalize_initial = normalize_initial;
+        self
+    }
+
+    /// Enable or disable a quirk of some legacy implementations, for bug-compatible
+    /// migrations. When enabled, the letter following the first letter is not dropped
+    /// when it has the same code as the first letter. By default, it is disabled,
+    /// as in standard Soundex.
+    ///
+    /// For instance, `P` and `F` both have code `1`, so standard Soundex encodes `Pfister`
+    /// as `P236` while the legacy quirk gives `P123`.
+    ///
+    /// # Parameter
+    ///
+    /// * `legacy_first_group`: if `true`, the quirk is reproduced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert_eq!(soundex.encode("Pfister"), "P236");
+    ///
+    /// let soundex = Soundex::default().legacy_first_group(true);
+    /// assert_eq!(soundex.encode("Pfister"), "P123");
+    /// ```
+    pub fn legacy_first_group(mut self, legacy_first_group: bool) -> Self {
+        self.legacy_first_group = legacy_first_group;
+        self
+    }
+
+    /// Enable or disable the encoding of the first letter. When enabled, the first letter
+    /// is replaced by its code (`0` for vowels and silent letters), so codes are fully numeric
+    /// with the default mapping. By default, it is disabled, as in standard Soundex the
+    /// first letter is kept.
+    ///
+    /// The first letter is still used to drop the next letters that have the same code,
+    /// and [normalize_initial](Soundex::normalize_initial) has no effect.
+    ///
+    /// # Parameter
+    ///
+    /// * `encode_first_letter`: if `true`, the first letter is encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    /// assert_eq!(soundex.encode("Robert"), "R163");
+    ///
+    /// let soundex = Soundex::default().encode_first_letter(true);
+    /// assert_eq!(soundex.encode("Robert"), "6163");
+    /// ```
+    pub fn encode_first_letter(mut self, encode_first_letter: bool) -> Self {
+        self.encode_first_letter = encode_first_letter;
+        self
+    }
+
+    /// Set how `H` and `W` are handled, see [HwRule]. By default, [Soundex::default] and
+    /// mappings with silent codes ignore them; otherwise `H` and `W` are coded by the
+    /// mapping, as it is given.
+    ///
+    /// With [HwRule::Separator], the code of `H` and `W` is replaced by `0`, as for vowels.
+    ///
+    /// # Parameter
+    ///
+    /// * `hw_rule`: how to handle `H` and `W`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, HwRule, Soundex};
+    ///
+    /// let soundex = Soundex::default().hw_rule(HwRule::Ignore);
+    /// assert_eq!(soundex.encode("Ashcroft"), "A261");
+    ///
+    /// let soundex = Soundex::default().hw_rule(HwRule::Separator);
+    /// assert_eq!(soundex.encode("Ashcroft"), "A226");
+    /// ```
+    pub fn hw_rule(mut self, hw_rule: HwRule) -> Self {
+        self.special_case_h_w = hw_rule == HwRule::Ignore;
+        self.hw_separator = hw_rule == HwRule::Separator;
+        self.ascii_table = build_ascii_table(self.coded_mapping());
+        self
+    }
+
+    /// Compute the shard of `value` by hashing its code, so values that
+    /// have the same code are in the same shard.
+    ///
+    /// The hash ([FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function))
+    /// is stable across runs, Rust versions and platforms.
+    ///
+    /// # Parameters
+    ///
+    /// * `value`: value to encode.
+    /// * `num_shards`: number of shards.
+    ///
+    /// # Return
+    ///
+    /// The shard of `value`, between `0` and `num_shards - 1`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `num_shards` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Soundex;
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.shard("Robert", 16), soundex.shard("Rupert", 16));
+    /// ```
+    pub fn shard(&self, value: &str, num_shards: usize) -> usize {
+        assert!(num_shards > 0, "num_shards must be greater than 0");
+
+        (fnv1a_64(self.encode(value).as_bytes()) % num_shards as u64) as usize
+    }
+
+    /// Return the words of `dictionary` that have `code` as code, in the same order.
+    ///
+    /// Every word of `dictionary` is encoded, so the cost is linear in the size of
+    /// `dictionary`. If you need many lookups on the same dictionary, consider indexing it
+    /// by code instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `code`: the code to look for.
+    /// * `dictionary`: candidate words.
+    ///
+    /// # Return
+    ///
+    /// Words of `dictionary` whose code is `code`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Soundex;
+    ///
+    /// let soundex = Soundex::default();
+    /// let dictionary = ["Robert", "Peter", "Rupert", "Rubin"];
+    ///
+    /// assert_eq!(soundex.suggest("R163", &dictionary), vec!["Robert", "Rupert"]);
+    /// ```
+    pub fn suggest<'a>(&self, code: &str, dictionary: &'a [&'a str]) -> Vec<&'a str> {
+        dictionary
+            .iter()
+            .filter(|word| self.encode(word) == code)
+            .copied()
+            .collect()
+    }
+
+    /// Encode each token of `text` and return the codes of each `window` consecutive
+    /// tokens, joined with a space, to index partial matches of multi-word inputs.
+    ///
+    /// Tokens are split as with [tokenize_name](crate::tokenize_name), tokens without a
+    /// code are ignored. If there are fewer codes than `window`, a single shingle with all
+    /// codes is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `text`: the text to encode.
+    /// * `window`: number of codes in a shingle.
+    ///
+    /// # Return
+    ///
+    /// Shingles, in the order of tokens. It is empty if `text` has no code or `window` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Soundex;
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(
+    ///     soundex.encode_shingles("John Michael Smith", 2),
+    ///     vec!["J500 M240", "M240 S530"]
+    /// );
+    /// ```
+    pub fn encode_shingles(&self, text: &str, window: usize) -> Vec<String> {
+        let codes: Vec<String> = tokenize_name(text)
+            .into_iter()
+            .map(|token| self.encode(token))
+            .filter(|code| !code.is_empty())
+            .collect();
+        if window == 0 || codes.is_empty() {
+            return Vec::new();
         }
+
+        codes
+            .windows(window.min(codes.len()))
+            .map(|shingle| shingle.join(" "))
+            .collect()
     }
 
+    /// Check if `code` is a well-formed code for this [Soundex], ie. a letter
+    /// followed by codes of the mapping or padding.
+    ///
+    /// # Parameter
+    ///
+    /// * `code`: the code to check.
+    ///
+    /// # Return
+    ///
+    /// `true` if `code` could have been produced by this [Soundex].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::Soundex;
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert!(soundex.is_valid_code("R163"));
+    /// assert!(!soundex.is_valid_code("R16"));
+    /// assert!(!soundex.is_valid_code("1163"));
+    /// ```
+    pub fn is_valid_code(&self, code: &str) -> bool {
+        let mut chars = code.chars();
+        let first_is_valid = match chars.next() {
+            Some(ch) if self.encode_first_letter => {
+                ch == PADDING || (ch != SILENT && self.mapping.contains(&ch))
+            }
+            Some(ch) if self.lowercase_output => ch.is_ascii_lowercase(),
+            Some(ch) => ch.is_ascii_uppercase(),
+            None => false,
+        };
+
+        first_is_valid
+            && self.is_valid_length(code.chars().count())
+            && chars.all(|ch| ch == PADDING || (ch != SILENT && self.mapping.contains(&ch)))
+    }
+
+    fn is_valid_length(&self, length: usize) -> bool {
+        if self.padding {
+            length == self.max_length
+        } else {
+            length <= self.max_length
+        }
+    }
+
+    /// The mapping, with `H` and `W` coded `0` if they are separators.
+    fn coded_mapping(&self) -> [char; 26] {
+        let mut mapping = self.mapping;
+        if self.hw_separator {
+            mapping[(b'H' - b'A') as usize] = '0';
+            mapping[(b'W' - b'A') as usize] = '0';
+        }
+        mapping
+    }
+
+    /// Letters outside of `A..Z` (eg. `É`) have no mapping, they are coded
+    /// like vowels.
     fn get_mapping_code(&self, ch: char) -> char {
-        self.mapping[ch as usize - 65]
+        if self.hw_separator && (ch == 'H' || ch == 'W') {
+            return '0';
+        }
+        self.mapping
+            .get((ch as usize).wrapping_sub('A' as usize))
+            .copied()
+            .unwrap_or('0')
     }
 }
 
@@ -107,20 +845,14 @@ impl Soundex {
 /// treatment for `H` and `W`̀: they are considered as silence.
 impl Default for Soundex {
     fn default() -> Self {
-        Self {
-            mapping: DEFAULT_US_ENGLISH_MAPPING_SOUNDEX,
-            special_case_h_w: true,
-        }
+        Self::new(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX, true)
     }
 }
 
 impl From<[char; 26]> for Soundex {
     fn from(mapping: [char; 26]) -> Self {
         let special_case_h_w = !has_silent_in_mapping(mapping);
-        Self {
-            mapping,
-            special_case_h_w,
-        }
+        Self::new(mapping, special_case_h_w)
     }
 }
 
@@ -214,36 +946,119 @@ impl TryFrom<String> for Soundex {
     /// #    Ok(())
     /// # }
     /// ```
-    fn try_from(mapping: String) -> Result<Self, Self::Error> {
-        mapping.as_str().parse::<Self>()
+    fn try_from(mapping: String) -> Result<Self, Self::Error> {
+        mapping.as_str().parse::<Self>()
+    }
+}
+
+impl Soundex {
+    /// Encode `value` and return a [SmolStr], so no heap allocation
+    /// is required.
+    ///
+    /// # Parameter
+    ///
+    /// * `value`: value to encode.
+    ///
+    /// # Return
+    ///
+    /// The code, same as [encode](Encoder::encode).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rphonetic::{Encoder, Soundex};
+    ///
+    /// let soundex = Soundex::default();
+    ///
+    /// assert_eq!(soundex.encode_small("jumped"), soundex.encode("jumped"));
+    /// ```
+    #[cfg(feature = "smol_str")]
+    pub fn encode_small(&self, value: &str) -> SmolStr {
+        encode_smol_str(|output| self.encode_with(value, output))
     }
-}
 
-impl Encoder for Soundex {
-    fn encode(&self, value: &str) -> String {
-        let value = Self::soundex_clean(value);
-        if value.is_empty() {
-            return value;
+    fn encode_with<O: Extend<char>>(&self, value: &str, output: &mut O) {
+        match &self.ascii_table {
+            Some(table) if value.is_ascii() && self.non_letter_policy == NonLetterPolicy::Drop => {
+                self.encode_ascii(value.as_bytes(), table, output)
+            }
+            _ => self.encode_chars(value, output),
+        }
+    }
+
+    /// Encode `value` without the ASCII table.
+    fn encode_chars<O: Extend<char>>(&self, value: &str, output: &mut O) {
+        self.encode_letters(
+            soundex_letters(value, self.non_letter_policy).map(|ch| match ch {
+                SEPARATOR => (ch, '0'),
+                _ => (ch, self.get_mapping_code(ch)),
+            }),
+            output,
+        )
+    }
+
+    /// Encode `value` using `table`. Non-ASCII bytes are skipped.
+    fn encode_ascii<O: Extend<char>>(&self, value: &[u8], table: &[u8; 256], output: &mut O) {
+        self.encode_letters(
+            value.iter().filter_map(|b| match table[*b as usize] {
+                0 => None,
+                code => Some((b.to_ascii_uppercase() as char, code as char)),
+            }),
+            output,
+        )
+    }
+
+    /// Compute the code from the letters of the value, uppercased,
+    /// along with their mapping code, and append it to `output`.
+    fn encode_letters<I, O>(&self, mut letters: I, output: &mut O)
+    where
+        I: Iterator<Item = (char, char)>,
+        O: Extend<char>,
+    {
+        let (first, first_code) = match letters.next() {
+            None => return,
+            Some(letter) => letter,
+        };
+        let mut previous = first_code;
+        if self.legacy_first_group {
+            // Forget the first letter's code, so the next letter is kept even if it shares it.
+            previous = '0';
+        }
+
+        let mut initial = first;
+        if self.encode_first_letter {
+            initial = if first_code == SILENT {
+                PADDING
+            } else {
+                first_code
+            };
+        } else {
+            if self.normalize_initial && first.is_ascii_uppercase() {
+                let digit = self.get_mapping_code(first);
+                if let Some(index) = self.mapping.iter().position(|code| *code == digit) {
+                    initial = (b'A' + index as u8) as char;
+                }
+            }
+
+            if self.lowercase_output {
+                initial = initial.to_ascii_lowercase();
+            }
         }
 
-        let mut code: [char; 4] = ['0', '0', '0', '0'];
-        code[0] = value.chars().next().unwrap();
+        output.extend(once(initial));
         let mut count = 1;
-        let mut previous = self.get_mapping_code(code[0]);
-        let mut iterator = value.chars().skip(1);
-        while count < code.len() {
-            match iterator.next() {
+        while count < self.max_length {
+            match letters.next() {
                 None => break,
-                Some(ch) => {
+                Some((ch, digit)) => {
                     if self.special_case_h_w && (ch == 'H' || ch == 'W') {
                         continue;
                     }
-                    let digit = self.get_mapping_code(ch);
                     if digit == SILENT {
                         continue;
                     }
                     if digit != '0' && digit != previous {
-                        code[count] = digit;
+                        output.extend(once(digit));
                         count += 1;
                     }
 
@@ -252,7 +1067,91 @@ impl Encoder for Soundex {
             }
         }
 
-        code.iter().collect()
+        if self.padding {
+            output.extend(repeat(PADDING).take(self.max_length - count));
+        }
+    }
+}
+
+impl Encoder for Soundex {
+    fn encode(&self, value: &str) -> String {
+        let mut code = String::with_capacity(self.max_length);
+        self.encode_into(value, &mut code);
+        code
+    }
+
+    fn encode_into(&self, value: &str, out: &mut String) {
+        out.clear();
+        self.encode_with(value, out);
+    }
+
+    fn encode_cow<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        // Without letters, the code is empty
+        if !value.chars().any(char::is_alphabetic) {
+            return Cow::Borrowed("");
+        }
+
+        let mut comparison = CodeComparison {
+            expected: value.chars(),
+            equal: true,
+        };
+        self.encode_with(value, &mut comparison);
+        if comparison.equal && comparison.expected.next().is_none() {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(self.encode(value))
+        }
+    }
+
+    /// Encode ASCII `value` without UTF-8 validation. Otherwise, it gives the same
+    /// code as [encode](Encoder::encode), invalid UTF-8 sequences are skipped.
+    fn encode_bytes(&self, value: &[u8]) -> Vec<u8> {
+        let mut code = String::with_capacity(self.max_length);
+        match &self.ascii_table {
+            Some(table) if value.is_ascii() && self.non_letter_policy == NonLetterPolicy::Drop => {
+                self.encode_ascii(value, table, &mut code)
+            }
+            _ => match core::str::from_utf8(value) {
+                Ok(value) => self.encode_chars(value, &mut code),
+                Err(_) => self.encode_chars(&skip_invalid_utf8(value), &mut code),
+            },
+        }
+
+        code.into_bytes()
+    }
+}
+
+/// Compare the chars of a code with `expected` while it is computed, so
+/// [Soundex::encode_cow] doesn't build the code to check it is `expected`.
+struct CodeComparison<'a> {
+    expected: core::str::Chars<'a>,
+    equal: bool,
+}
+
+impl Extend<char> for CodeComparison<'_> {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        for ch in iter {
+            self.equal = self.equal && self.expected.next() == Some(ch);
+        }
+    }
+}
+
+/// Decode `value` as UTF-8, skipping invalid sequences.
+fn skip_invalid_utf8(mut value: &[u8]) -> String {
+    let mut result = String::with_capacity(value.len());
+    loop {
+        match core::str::from_utf8(value) {
+            Ok(valid) => {
+                result.push_str(valid);
+                return result;
+            }
+            Err(error) => {
+                let (valid, invalid) = value.split_at(error.valid_up_to());
+                // Bytes before `valid_up_to` are valid UTF-8
+                result.push_str(core::str::from_utf8(valid).unwrap());
+                value = &invalid[error.error_len().unwrap_or(invalid.len())..];
+            }
+        }
     }
 }
 
@@ -262,7 +1161,9 @@ impl SoundexCommons for Soundex {}
 
 #[cfg(test)]
 mod tests {
+
     use super::*;
+    use crate::soundex_fixtures::SOUNDEX_TEST_DATA;
 
     fn check_encoding(data: Vec<&str>, expected: &str) {
         let soundex = Soundex::default();
@@ -516,6 +1417,554 @@ mod tests {
         assert_eq!(soundex.encode("Dwdds"), "D320");
     }
 
+    #[test]
+    fn test_lowercase_output() {
+        let soundex = Soundex::default().lowercase_output(true);
+
+        assert_eq!(soundex.encode("Robert"), "r163");
+        assert_eq!(soundex.encode("Lee"), "l000");
+        assert_eq!(soundex.encode(""), "");
+
+        let soundex = soundex.lowercase_output(false);
+        assert_eq!(soundex.encode("Robert"), "R163");
+    }
+
+    #[test]
+    fn test_non_letter_policy() {
+        let soundex = Soundex::default();
+        assert_eq!(soundex.encode("Van-Dyke"), "V532");
+        assert_eq!(soundex.encode("Ash-Croft"), "A261");
+
+        let soundex = soundex.non_letter_policy(NonLetterPolicy::Separator);
+        assert_eq!(soundex.encode("Van-Dyke"), "V532");
+        assert_eq!(soundex.encode("Ash-Croft"), "A226");
+        assert_eq!(soundex.encode("Ash Croft"), "A226");
+        assert_eq!(soundex.encode("Pf1ster"), "P236");
+        assert_eq!(soundex.encode(" -Ashcroft- "), "A261");
+        assert_eq!(soundex.encode("--"), "");
+    }
+
+    #[test]
+    fn test_from_groups() -> Result<(), PhoneticError> {
+        let soundex = Soundex::from_groups(&[
+            ("AEIOUYHW", "0"),
+            ("BFPV", "1"),
+            ("CGJKQSXZ", "2"),
+            ("DT", "3"),
+            ("L", "4"),
+            ("MN", "5"),
+            ("R", "6"),
+        ])?;
+        assert_eq!(soundex.mapping, DEFAULT_US_ENGLISH_MAPPING_SOUNDEX);
+        assert_eq!(soundex, Soundex::default());
+
+        let soundex = Soundex::from_groups(&[
+            ("aeiouy", "-"),
+            ("hw", "-"),
+            ("bfpv", "1"),
+            ("cgjkqsxz", "2"),
+            ("dt", "3"),
+            ("l", "4"),
+            ("mn", "5"),
+            ("r", "6"),
+        ])?;
+        assert_eq!(
+            soundex.mapping,
+            DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX
+        );
+        assert_eq!(
+            soundex,
+            Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_mapping() -> Result<(), PhoneticError> {
+        let soundex = Soundex::from_mapping("01230120022455012623010202")?;
+        assert_eq!(soundex, Soundex::default());
+
+        let soundex = Soundex::from_mapping("-123-12--22455-12623-1-2-2")?;
+        assert_eq!(
+            soundex,
+            Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX)
+        );
+
+        // Custom mapping, with letters
+        let soundex = Soundex::from_mapping("0BCD0BC00CCLMM0BCR0D0B0C0C")?;
+        assert_eq!(soundex.encode("jumped"), "JMBD");
+        assert_eq!(soundex.encode("Robert"), "RBRD");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_mapping_errors() {
+        match Soundex::from_mapping("0123012") {
+            Err(PhoneticError::InvalidMapping { reason }) => {
+                assert_eq!(reason, "mapping must contain 26 codes, found 7");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        match Soundex::from_mapping("0123012002245501262301020") {
+            Err(error @ PhoneticError::InvalidMapping { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "Invalid mapping : mapping must contain 26 codes, found 25"
+                );
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        assert!(Soundex::from_mapping("").is_err());
+        assert!(Soundex::from_mapping("012301200224550126230102020").is_err());
+
+        match Soundex::from_mapping("01230120022455012623 10202") {
+            Err(PhoneticError::InvalidMapping { reason }) => {
+                assert_eq!(reason, "invalid code ' ' for letter 'U' at position 21");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        assert!(Soundex::from_mapping("0123012002245501262301020\u{00E9}").is_err());
+    }
+
+    #[test]
+    fn test_from_groups_errors() {
+        let groups = [
+            ("AEIOUYHW", "0"),
+            ("BFPV", "1"),
+            ("CGJKQSXZ", "2"),
+            ("DT", "3"),
+            ("L", "4"),
+            ("MN", "5"),
+        ];
+
+        match Soundex::from_groups(&groups) {
+            Err(PhoneticError::ParseRuleError(error)) => {
+                assert_eq!(error.line_number, 0);
+                assert_eq!(error.line_content, "R");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        let mut with_duplicate = groups.to_vec();
+        with_duplicate.push(("RB", "6"));
+        match Soundex::from_groups(&with_duplicate) {
+            Err(PhoneticError::ParseRuleError(error)) => {
+                assert_eq!(error.line_number, 7);
+                assert_eq!(error.line_content, "(\"RB\", \"6\")");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+
+        let mut with_long_code = groups.to_vec();
+        with_long_code.push(("R", "66"));
+        assert!(Soundex::from_groups(&with_long_code).is_err());
+
+        let mut with_empty_code = groups.to_vec();
+        with_empty_code.push(("R", ""));
+        assert!(Soundex::from_groups(&with_empty_code).is_err());
+
+        let mut with_non_letter = groups.to_vec();
+        with_non_letter.push(("R-", "6"));
+        assert!(Soundex::from_groups(&with_non_letter).is_err());
+    }
+
+    #[cfg(feature = "smol_str")]
+    #[test]
+    fn test_encode_small() {
+        let soundexes = [
+            Soundex::default(),
+            Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX),
+            Soundex::default().lowercase_output(true),
+            Soundex::default().non_letter_policy(NonLetterPolicy::Separator),
+        ];
+        let values = [
+            "",
+            "Robert",
+            "Rupert",
+            "Ashcraft",
+            "Ash-Croft",
+            "O'Hara",
+            "jumped",
+            "Lee",
+            "  ",
+            "123",
+        ];
+
+        for soundex in soundexes.iter() {
+            for value in values {
+                let code = soundex.encode_small(value);
+                assert_eq!(code, soundex.encode(value), "Error for {value:?}");
+                assert!(!code.is_heap_allocated());
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_initial() {
+        let soundex = Soundex::default();
+        assert_eq!(soundex.encode("Carl"), "C640");
+        assert_eq!(soundex.encode("Karl"), "K640");
+
+        let soundex = soundex.normalize_initial(true);
+        assert_eq!(soundex.encode("Carl"), "C640");
+        assert_eq!(soundex.encode("Karl"), "C640");
+        assert_eq!(soundex.encode("Srl"), "C640");
+        assert_eq!(soundex.encode("Peter"), "B360");
+        assert_eq!(soundex.encode("Eric"), "A620");
+        assert_eq!(soundex.encode("Lee"), "L000");
+        assert_eq!(soundex.encode(""), "");
+        assert!(soundex.is_encoded_equals("Carl", "Karl"));
+        assert_eq!(soundex.encode("Karl"), "C640");
+
+        let soundex = soundex.lowercase_output(true);
+        assert_eq!(soundex.encode("Karl"), "c640");
+    }
+
+    #[test]
+    fn test_legacy_first_group() {
+        let standard = Soundex::default();
+        let legacy = Soundex::default().legacy_first_group(true);
+
+        // Second letter shares the first letter's code
+        assert_eq!(standard.encode("Pfister"), "P236");
+        assert_eq!(legacy.encode("Pfister"), "P123");
+        assert_eq!(standard.encode("Lloyd"), "L300");
+        assert_eq!(legacy.encode("Lloyd"), "L430");
+        assert_eq!(standard.encode("Schmidt"), "S530");
+        assert_eq!(legacy.encode("Schmidt"), "S253");
+
+        // No divergence otherwise
+        for value in ["Robert", "Rupert", "Ashcraft", "Tymczak", "Lee", "A", ""] {
+            assert_eq!(
+                legacy.encode(value),
+                standard.encode(value),
+                "Error for {value}"
+            );
+        }
+
+        let legacy = legacy.lowercase_output(true);
+        assert_eq!(legacy.encode("Pfister"), "p123");
+    }
+
+    #[test]
+    fn test_hw_rule() {
+        let standard = Soundex::default();
+        let ignore = Soundex::default().hw_rule(HwRule::Ignore);
+        let separator = Soundex::default().hw_rule(HwRule::Separator);
+
+        assert_eq!(ignore, standard);
+        for value in ["Ashcraft", "Ashcroft"] {
+            assert_eq!(ignore.encode(value), "A261", "Error for {value}");
+            assert_eq!(separator.encode(value), "A226", "Error for {value}");
+            assert_eq!(separator.encode_bytes(value.as_bytes()), b"A226");
+        }
+        assert!(separator.is_encoded_equals("Ashcraft", "Ashcroft"));
+        assert_eq!(ignore.encode("Schwarz"), "S620");
+        assert_eq!(separator.encode("Schwarz"), "S620");
+        assert_eq!(ignore.encode("Tymczak"), "T522");
+        assert_eq!(separator.encode("Tymczak"), "T522");
+        // "Bwbach" : W is between two B
+        assert_eq!(ignore.encode("Bwbach"), "B200");
+        assert_eq!(separator.encode("Bwbach"), "B120");
+        // "Burroughs" : H is between G and S
+        assert_eq!(ignore.encode("Burroughs"), "B620");
+        assert_eq!(separator.encode("Burroughs"), "B622");
+
+        // With silent codes, H and W are ignored unless they are separators
+        let genealogy = Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX);
+        assert_eq!(genealogy.encode("Ashcraft"), "A261");
+        assert_eq!(genealogy.hw_rule(HwRule::Ignore).encode("Ashcraft"), "A261");
+        assert_eq!(
+            genealogy.hw_rule(HwRule::Separator).encode("Ashcraft"),
+            "A226"
+        );
+
+        // Without special case, H and W are coded by the mapping
+        let mapping = Soundex::new(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX, false);
+        assert_eq!(mapping.encode("Ashcraft"), "A226");
+        assert_eq!(mapping.hw_rule(HwRule::Ignore).encode("Ashcraft"), "A261");
+
+        // The mapping is kept, so the rule can be changed back
+        assert_eq!(separator.hw_rule(HwRule::Ignore), standard);
+        assert_eq!(
+            genealogy
+                .hw_rule(HwRule::Separator)
+                .hw_rule(HwRule::Ignore)
+                .encode("Ashcraft"),
+            "A261"
+        );
+
+        let json = serde_json::to_string(&separator).unwrap();
+        let deserialized: Soundex = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, separator);
+        assert_eq!(deserialized.encode("Ashcraft"), "A226");
+    }
+
+    #[test]
+    fn test_encode_first_letter() {
+        let standard = Soundex::default();
+        let numeric = Soundex::default().encode_first_letter(true);
+
+        assert_eq!(standard.encode("Robert"), "R163");
+        assert_eq!(numeric.encode("Robert"), "6163");
+        assert_eq!(numeric.encode("Rupert"), "6163");
+        assert_eq!(numeric.encode("Pfister"), "1236");
+        assert_eq!(numeric.encode("Ashcraft"), "0261");
+        assert_eq!(numeric.encode("Lee"), "4000");
+        assert_eq!(numeric.encode(""), "");
+        assert_eq!(numeric.encode_bytes(b"Robert"), b"6163");
+        assert_eq!(numeric.encode_first_letter(false), standard);
+
+        assert_eq!(numeric.difference("Robert", "Rupert"), 4);
+        // Initials with the same code now match
+        assert_eq!(standard.difference("Carl", "Karl"), 3);
+        assert_eq!(numeric.difference("Carl", "Karl"), 4);
+        assert_eq!(numeric.difference("Robert", "Peter"), 1);
+        assert!(numeric.is_valid_code("6163"));
+        assert!(!numeric.is_valid_code("R163"));
+
+        // Silent first letter, and the legacy quirk that keeps the second letter
+        let genealogy =
+            Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX).encode_first_letter(true);
+        assert_eq!(genealogy.encode("Ashcraft"), "0261");
+        let legacy = numeric.legacy_first_group(true);
+        assert_eq!(legacy.encode("Pfister"), "1123");
+
+        // Not affected by options on the first letter
+        let numeric = numeric.normalize_initial(true).lowercase_output(true);
+        assert_eq!(numeric.encode("Robert"), "6163");
+    }
+
+    #[test]
+    fn test_shard() {
+        let soundex = Soundex::default();
+
+        assert_eq!(soundex.shard("Robert", 16), soundex.shard("Rupert", 16));
+        assert_eq!(soundex.shard("Smith", 7), soundex.shard("Smyth", 7));
+        assert!(soundex.shard("Ashcraft", 3) < 3);
+        assert_eq!(soundex.shard("Robert", 1), 0);
+
+        // Stable across runs
+        assert_eq!(
+            soundex.shard("Robert", 1024),
+            (fnv1a_64(b"R163") % 1024) as usize
+        );
+        assert_eq!(soundex.shard("Robert", 1000), 991);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shard_zero() {
+        Soundex::default().shard("Robert", 0);
+    }
+
+    #[test]
+    fn test_encode_any() {
+        let soundex = Soundex::default();
+        let value = String::from("Robert");
+
+        assert_eq!(soundex.encode_any("Robert"), "R163");
+        assert_eq!(soundex.encode_any(&value), "R163");
+        assert_eq!(soundex.encode_any(Cow::Borrowed("Robert")), "R163");
+        assert_eq!(soundex.encode_any(Cow::<str>::Owned(value.clone())), "R163");
+        assert_eq!(soundex.encode_any(value), "R163");
+    }
+
+    #[test]
+    fn test_is_valid_code() {
+        let soundex = Soundex::default();
+
+        assert!(soundex.is_valid_code("R163"));
+        assert!(soundex.is_valid_code("L000"));
+        assert!(!soundex.is_valid_code("R16"));
+        assert!(!soundex.is_valid_code("R1634"));
+        assert!(!soundex.is_valid_code("1163"));
+        assert!(!soundex.is_valid_code("r163"));
+        assert!(!soundex.is_valid_code("R1A3"));
+        assert!(!soundex.is_valid_code("R7"));
+        assert!(!soundex.is_valid_code(""));
+
+        let soundex = soundex.lowercase_output(true);
+        assert!(soundex.is_valid_code("r163"));
+        assert!(!soundex.is_valid_code("R163"));
+
+        let soundex = Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX);
+        assert!(soundex.is_valid_code("H251"));
+        assert!(!soundex.is_valid_code("H-51"));
+    }
+
+    #[test]
+    fn test_ascii_table() {
+        // Simple linear congruential generator to have reproducible values.
+        let mut seed: u64 = 42;
+        let mut next = move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+
+        let soundexes = [
+            Soundex::default(),
+            Soundex::from(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX),
+            Soundex::new(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX, false).lowercase_output(true),
+        ];
+        for _ in 0..10_000 {
+            let length = next() % 12;
+            let value: String = (0..length)
+                .map(|_| {
+                    // Favor letters
+                    if next() % 4 == 0 {
+                        (next() % 128) as u8 as char
+                    } else {
+                        (b'A' + (next() % 26) as u8 + (next() % 2) as u8 * 32) as char
+                    }
+                })
+                .collect();
+            for soundex in soundexes.iter() {
+                let mut chars = String::new();
+                soundex.encode_chars(&value, &mut chars);
+                assert_eq!(soundex.encode(&value), chars, "Error for {value:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_without_ascii_table() {
+        let mut mapping = DEFAULT_US_ENGLISH_MAPPING_SOUNDEX;
+        mapping[0] = 'α';
+        let soundex = Soundex::new(mapping, true);
+
+        assert!(soundex.ascii_table.is_none());
+        assert_eq!(soundex.encode("Robert"), "R163");
+        assert_eq!(soundex.encode("Bashcraft"), "Bα26");
+    }
+
+    #[test]
+    fn test_max_length() {
+        let soundex = Soundex::default();
+        assert_eq!(soundex.encode("Washington"), "W252");
+
+        let soundex = Soundex::with_length(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX, 6);
+        assert_eq!(soundex, Soundex::default().max_length(6));
+        assert_eq!(soundex.encode("Washington"), "W25235");
+        assert_eq!(soundex.encode("Ashcraft"), "A26130");
+        assert!(soundex.is_valid_code("W25235"));
+        assert!(!soundex.is_valid_code("W252"));
+
+        // Padding
+        assert_eq!(soundex.encode("Lee"), "L00000");
+        assert_eq!(soundex.encode("A"), "A00000");
+        assert_eq!(soundex.encode(""), "");
+
+        let soundex = Soundex::default().max_length(1);
+        assert_eq!(soundex.encode("Washington"), "W");
+
+        let soundex = Soundex::default().max_length(12);
+        assert_eq!(soundex.encode("Washington"), "W25235000000");
+    }
+
+    #[test]
+    fn test_code_edit_distance() {
+        let soundex = Soundex::default();
+
+        // R163 and R163
+        assert_eq!(soundex.code_edit_distance("Robert", "Rupert"), 0);
+        // R163 and R150
+        assert_eq!(soundex.code_edit_distance("Robert", "Rubin"), 2);
+        // A226 and A261
+        assert_eq!(
+            soundex
+                .non_letter_policy(NonLetterPolicy::Separator)
+                .code_edit_distance("Ash-Croft", "Ashcroft"),
+            2
+        );
+        // L000 and empty code
+        assert_eq!(soundex.code_edit_distance("Lee", ""), 4);
+
+        let soundex = soundex.padding(false);
+        // L and L3
+        assert_eq!(soundex.code_edit_distance("Lee", "Lloyd"), 1);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let soundex = Soundex::default();
+        let dictionary = [
+            "Robert", "Peter", "Rupert", "Rubin", "Ashcraft", "Robbert", "Ralph",
+        ];
+
+        assert_eq!(
+            soundex.suggest("R163", &dictionary),
+            vec!["Robert", "Rupert", "Robbert"]
+        );
+        assert_eq!(soundex.suggest("A261", &dictionary), vec!["Ashcraft"]);
+        assert!(soundex.suggest("Z000", &dictionary).is_empty());
+        assert!(soundex.suggest("R163", &[]).is_empty());
+
+        let soundex = soundex.lowercase_output(true);
+        assert_eq!(soundex.suggest("r410", &dictionary), vec!["Ralph"]);
+    }
+
+    #[test]
+    fn test_padding() {
+        let padded = Soundex::default();
+        let raw = Soundex::default().padding(false);
+
+        assert_eq!(padded.encode("Lee"), "L000");
+        assert_eq!(raw.encode("Lee"), "L");
+        assert_eq!(padded.encode("Rice"), "R200");
+        assert_eq!(raw.encode("Rice"), "R2");
+        assert_eq!(raw.encode("Robert"), "R163");
+        assert_eq!(raw.encode("Washington"), "W252");
+        assert_eq!(raw.encode(""), "");
+        assert_eq!(raw.max_length(6).encode("Ashcraft"), "A2613");
+
+        assert!(raw.is_valid_code("L"));
+        assert!(raw.is_valid_code("R2"));
+        assert!(raw.is_valid_code("R163"));
+        assert!(!raw.is_valid_code("R1634"));
+        assert!(!padded.is_valid_code("R2"));
+
+        // Codes have different lengths
+        assert_eq!(raw.difference("Lee", "Lloyd"), 1);
+        assert_eq!(raw.difference("Lloyd", "Lee"), 1);
+        assert_eq!(raw.difference("Rice", "Rich"), 2);
+        assert_eq!(raw.difference("Rice", "Robert"), 1);
+        assert_eq!(raw.difference("Robert", "Rupert"), 4);
+        assert_eq!(padded.difference("Lee", "Lloyd"), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_length_zero() {
+        let _ = Soundex::default().max_length(0);
+    }
+
+    #[test]
+    fn test_strict() -> Result<(), Vec<char>> {
+        let soundex = Soundex::strict(DEFAULT_US_ENGLISH_MAPPING_SOUNDEX)?;
+
+        assert_eq!(soundex.try_encode("José"), Err('é'));
+        assert_eq!(soundex.try_encode("Robert"), Ok("R163".to_string()));
+        assert_eq!(soundex.try_encode("O'Brien"), Ok("O165".to_string()));
+        assert_eq!(soundex.try_encode(""), Ok("".to_string()));
+
+        let soundex = Soundex::strict(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX)?;
+        assert_eq!(soundex.try_encode("Dodds"), Ok("D200".to_string()));
+
+        let mut mapping = DEFAULT_US_ENGLISH_MAPPING_SOUNDEX;
+        mapping[1] = 'β';
+        assert_eq!(Soundex::strict(mapping), Err(mapping.to_vec()));
+
+        Ok(())
+    }
+
     #[test]
     fn test_try_from_str() -> Result<(), Vec<char>> {
         let result = Soundex::try_from("01230120022455012623010202")?;
@@ -533,4 +1982,189 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn check_soundex_fixture() {
+        let soundex = Soundex::default();
+
+        for (i, (value, expected, _)) in SOUNDEX_TEST_DATA.iter().enumerate() {
+            assert_eq!(soundex.encode(value), *expected, "[{i}] {value} fail");
+        }
+    }
+
+    #[test]
+    fn test_encode_into() {
+        let encoder = Soundex::default();
+        let mut buffer = String::from("previous content");
+
+        for (value, _, _) in SOUNDEX_TEST_DATA.iter() {
+            encoder.encode_into(value, &mut buffer);
+            assert_eq!(buffer, encoder.encode(value), "{value} fail");
+        }
+        encoder.encode_into("", &mut buffer);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_encode_all() {
+        let soundex = Soundex::default();
+        let names: Vec<&str> = SOUNDEX_TEST_DATA.iter().map(|(name, _, _)| *name).collect();
+
+        let codes = soundex.encode_all(names.iter().copied());
+        assert_eq!(codes.len(), names.len());
+        for (name, code) in names.iter().zip(codes.iter()) {
+            assert_eq!(*code, soundex.encode(name), "{name} fail");
+        }
+
+        let iter = soundex.encode_iter(names.iter().copied());
+        assert_eq!(iter.len(), names.len());
+        for (name, code) in names.iter().zip(iter) {
+            assert_eq!(code, soundex.encode(name), "{name} fail");
+        }
+
+        let last = soundex.encode_iter(names.iter().copied()).next_back();
+        assert_eq!(last, names.last().map(|name| soundex.encode(name)));
+        assert!(soundex.encode_all(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_encode_opt() {
+        let encoder = Soundex::default();
+
+        assert_eq!(encoder.encode_opt("Robert"), Some(encoder.encode("Robert")));
+        assert_eq!(encoder.encode_opt("123"), None);
+        assert_eq!(encoder.encode_opt(""), None);
+        assert_eq!(encoder.encode_opt(" -. "), None);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_encode_all() {
+        let soundex = Soundex::default();
+        let mut names: Vec<&str> = SOUNDEX_TEST_DATA.iter().map(|(name, _, _)| *name).collect();
+        // Fisher-Yates shuffle with a fixed seed, so the test is reproducible
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for i in (1..names.len()).rev() {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            names.swap(i, (seed >> 33) as usize % (i + 1));
+        }
+
+        assert_eq!(
+            soundex.par_encode_all(&names),
+            soundex.encode_all(names.iter().copied())
+        );
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        let soundex = Soundex::default();
+        let separator = Soundex::default().non_letter_policy(NonLetterPolicy::Separator);
+        for value in [
+            "Robert",
+            "Rupert",
+            "Ashcraft",
+            "Tymczak",
+            "",
+            "O'Hara",
+            "Ash-Croft",
+            "Pf\u{00E9}fer",
+            "Ro\u{00E9}bert",
+        ] {
+            assert_eq!(
+                soundex.encode_bytes(value.as_bytes()),
+                soundex.encode(value).as_bytes()
+            );
+            assert_eq!(
+                separator.encode_bytes(value.as_bytes()),
+                separator.encode(value).as_bytes()
+            );
+        }
+
+        // Invalid UTF-8 sequences are skipped
+        assert_eq!(soundex.encode_bytes(b"Ro\xFF\xFEbert"), b"R163");
+        assert_eq!(separator.encode_bytes(b"Ro\xFF\xFEbert"), b"R163");
+        assert_eq!(soundex.encode_bytes(b"Pf\xC3\xA9f\xC3er"), b"P160");
+    }
+
+    #[test]
+    fn test_encode_cow() {
+        let data = [
+            Soundex::default(),
+            Soundex::default().padding(false),
+            Soundex::default().lowercase_output(true),
+            Soundex::default().max_length(6),
+        ];
+        for soundex in data {
+            for (value, _, _) in SOUNDEX_TEST_DATA.iter() {
+                assert_eq!(soundex.encode_cow(value), soundex.encode(value), "{value}");
+            }
+            for value in ["", "   ", "123", "R163", "r163", "R", "Robert"] {
+                assert_eq!(soundex.encode_cow(value), soundex.encode(value), "{value}");
+            }
+        }
+
+        let soundex = Soundex::default();
+        assert!(matches!(soundex.encode_cow("R000"), Cow::Borrowed(_)));
+        assert!(matches!(soundex.encode_cow("R0000"), Cow::Owned(_)));
+        assert!(matches!(soundex.encode_cow("R00"), Cow::Owned(_)));
+        assert!(matches!(soundex.encode_cow(""), Cow::Borrowed(_)));
+        assert!(matches!(soundex.encode_cow(" 42 "), Cow::Borrowed(_)));
+        assert!(matches!(soundex.encode_cow("Robert"), Cow::Owned(_)));
+        // Default implementation
+        assert_eq!(
+            crate::RefinedSoundex::default().encode_cow("Robert"),
+            "R901096"
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let soundex = Soundex::default()
+            .max_length(6)
+            .padding(false)
+            .lowercase_output(true);
+        let json = serde_json::to_string(&soundex).unwrap();
+        let deserialized: Soundex = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, soundex);
+        assert_eq!(deserialized.encode("Washington"), "w25235");
+        assert_eq!(deserialized.encode("Lee"), "l");
+
+        let soundex =
+            Soundex::new(DEFAULT_US_ENGLISH_GENEALOGY_MAPPING_SOUNDEX, false).max_length(8);
+        let json = serde_json::to_string(&soundex).unwrap();
+        let deserialized: Soundex = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, soundex);
+        assert_eq!(deserialized.encode("Williams"), soundex.encode("Williams"));
+    }
+
+    #[test]
+    fn test_deserialize_max_length_zero() {
+        let mut json = serde_json::to_value(Soundex::default()).unwrap();
+        json["max_length"] = serde_json::Value::from(0);
+        let result: Result<Soundex, _> = serde_json::from_value(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_encode_shingles() {
+        let soundex = Soundex::default();
+
+        assert_eq!(
+            soundex.encode_shingles("John Michael Smith", 2),
+            vec!["J500 M240", "M240 S530"]
+        );
+        assert_eq!(
+            soundex.encode_shingles("John Michael Smith", 1),
+            vec!["J500", "M240", "S530"]
+        );
+        assert_eq!(
+            soundex.encode_shingles("John-Michael  Smith, 42", 3),
+            vec!["J500 M240 S530"]
+        );
+        assert_eq!(soundex.encode_shingles("John Smith", 5), vec!["J500 S530"]);
+        assert!(soundex.encode_shingles("John Smith", 0).is_empty());
+        assert!(soundex.encode_shingles(" 42 ", 2).is_empty());
+    }
 }